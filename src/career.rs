@@ -0,0 +1,739 @@
+use crate::error::{RosterError, SimulationError};
+use crate::events::EventStream;
+use crate::generator::generate_driver;
+use crate::market;
+use crate::models::{
+    Car, Circuit, Driver, DriverId, Substitution, SubstitutionReason, SubstitutionScope, Team,
+    TeamId,
+};
+use crate::reporter::Reporter;
+use crate::roster::Roster;
+use crate::session::{Qualifying, Race, Session, SessionInput, session_rng};
+use crate::utils::*;
+use crate::world::World;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::io;
+
+/// Title sponsors that put their name in front of a team's, like "Stake F1 Team Kick Sauber".
+const TITLE_SPONSORS: [&str; 6] = [
+    "Stake F1 Team",
+    "Visa Cash App",
+    "MoneyGram",
+    "BWT",
+    "Oracle",
+    "Aramco",
+];
+
+/// Area of the car a team puts its development budget into over the winter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DevelopmentFocus {
+    EnginePower,
+    TyreManagement,
+    Reliability,
+    Aerodynamics,
+}
+
+impl DevelopmentFocus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DevelopmentFocus::EnginePower => "Engine Power",
+            DevelopmentFocus::TyreManagement => "Tyre Management",
+            DevelopmentFocus::Reliability => "Reliability",
+            DevelopmentFocus::Aerodynamics => "Aerodynamics",
+        }
+    }
+
+    /// The car's weakest area, which is where AI teams put their money.
+    pub fn weakest_area(car: &Car) -> DevelopmentFocus {
+        let areas = [
+            (DevelopmentFocus::EnginePower, car.engine_power),
+            (DevelopmentFocus::TyreManagement, car.tyre_management),
+            (DevelopmentFocus::Reliability, car.reliability),
+            (DevelopmentFocus::Aerodynamics, car.aerodynamics),
+        ];
+
+        areas
+            .iter()
+            .min_by_key(|(_, value)| *value)
+            .map(|(focus, _)| *focus)
+            .unwrap_or(DevelopmentFocus::Aerodynamics)
+    }
+}
+
+/// A driver's season in numbers, credited to whoever actually drove the car.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriverStats {
+    pub driver: DriverId,
+    pub team: TeamId,
+    pub starts: u32,
+    pub wins: u32,
+    pub podiums: u32,
+    pub points: u32,
+    pub dnfs: u32,
+}
+
+impl DriverStats {
+    pub fn info(&self, world: &World) -> String {
+        format!(
+            "{} ({}) - {} pts | {} starts | {} wins | {} podiums | {} DNFs",
+            world.driver_name(self.driver),
+            world.team_name(self.team),
+            self.points,
+            self.starts,
+            self.wins,
+            self.podiums,
+            self.dnfs
+        )
+    }
+}
+
+/// Final standings and notable facts of a completed season.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonRecord {
+    pub season: u16,
+    pub rounds: Vec<String>,
+    pub driver_standings: Vec<(DriverId, u32)>,
+    pub constructor_standings: Vec<(TeamId, u32)>,
+    pub driver_stats: Vec<DriverStats>,
+    pub substitutions: Vec<Substitution>,
+    pub player_team_position: usize,
+}
+
+impl SeasonRecord {
+    pub fn info(&self, world: &World) -> String {
+        let driver_champion = self
+            .driver_standings
+            .first()
+            .map(|(driver, points)| format!("{} ({} pts)", world.driver_name(*driver), points))
+            .unwrap_or_default();
+        let constructor_champion = self
+            .constructor_standings
+            .first()
+            .map(|(team, points)| format!("{} ({} pts)", world.team_name(*team), points))
+            .unwrap_or_default();
+
+        format!(
+            "📅 {} - {} rounds | 🏆 Drivers: {} | 🏗️ Constructors: {} | Your team: P{}",
+            self.season,
+            self.rounds.len(),
+            driver_champion,
+            constructor_champion,
+            self.player_team_position
+        )
+    }
+}
+
+/// Whole world state of a career, carried from season to season and stored in the save file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Career {
+    pub season: u16,
+    pub player_team: TeamId,
+    pub world: World,
+    pub calendar: Vec<Circuit>,
+    pub reserve_circuits: Vec<Circuit>,
+    pub finances: Vec<(TeamId, f32)>, // bank balance per team in millions of USD
+    pub history: Vec<SeasonRecord>,
+}
+
+impl Career {
+    /// Starts a new career from `roster`, managing `player_team`.
+    pub fn new(player_team: TeamId, roster: &Roster) -> Result<Career, RosterError> {
+        let world = roster.to_world()?;
+        let finances = world
+            .teams()
+            .iter()
+            .map(|team| (team.id, team.driver_budget * 2.0 + 60.0))
+            .collect();
+
+        Ok(Career {
+            season: roster.season,
+            player_team,
+            world,
+            calendar: create_calendar(),
+            reserve_circuits: create_reserve_circuits(),
+            finances,
+            history: Vec::new(),
+        })
+    }
+
+    /// Loads a saved career. The world in the save goes through the same checks as a roster,
+    /// so a hand-edited save can't put impossible attributes or seats into the simulation. The
+    /// player's team has to be on the grid and every team needs exactly one bank balance.
+    pub fn load(path: &str) -> Result<Career, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        let career: Career = serde_json::from_str(&contents)?;
+        Roster::from_world(career.season, &career.world)?;
+
+        let teams = career.world.team_ids();
+        if !teams.contains(&career.player_team) {
+            return Err(format!(
+                "{} has the player managing unknown team #{}",
+                path, career.player_team.0
+            )
+            .into());
+        }
+        for team in &teams {
+            let entries = career.finances.iter().filter(|(id, _)| id == team).count();
+            if entries != 1 {
+                return Err(format!(
+                    "{} has {} bank balances for {}, not 1",
+                    path,
+                    entries,
+                    career.world.team_name(*team)
+                )
+                .into());
+            }
+        }
+        if let Some((id, _)) = career.finances.iter().find(|(id, _)| !teams.contains(id)) {
+            return Err(format!("{} has a bank balance for unknown team #{}", path, id.0).into());
+        }
+
+        Ok(career)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    pub fn player_team(&self) -> &Team {
+        self.world.team(self.player_team)
+    }
+
+    pub fn balance(&self, team: TeamId) -> f32 {
+        self.finances
+            .iter()
+            .find(|(id, _)| *id == team)
+            .map(|(_, balance)| *balance)
+            .unwrap_or(0.0)
+    }
+
+    /// Runs every round on the calendar and records the final standings in the history. Each
+    /// round's race goes to `reporter`.
+    pub fn simulate_season(
+        &mut self,
+        reporter: &mut dyn Reporter,
+    ) -> Result<SeasonRecord, SimulationError> {
+        let mut driver_standings: Vec<(DriverId, u32)> = self
+            .world
+            .race_drivers()
+            .into_iter()
+            .map(|driver| (driver, 0))
+            .collect();
+        let mut constructor_standings: Vec<(TeamId, u32)> = self
+            .world
+            .team_ids()
+            .into_iter()
+            .map(|team| (team, 0))
+            .collect();
+
+        let mut driver_stats: Vec<DriverStats> = Vec::new();
+        let mut season_substitutions = Vec::new();
+
+        for (round, circuit) in self.calendar.iter().enumerate() {
+            reporter.note(&format!("\n🏁 Round {} - {} 🏁", round + 1, circuit.info()));
+
+            // Now and then a driver is injured or ill and the reserve steps in
+            let substitutions = random_substitutions(&self.world);
+            for substitution in &substitutions {
+                reporter.note(&substitution.info(&self.world));
+            }
+            let entrants = session_entrants(&self.world, &substitutions, "Race");
+
+            let round_seed: u64 = rand::random();
+            let mut setups = practice_setups(
+                &self.world,
+                &entrants,
+                circuit,
+                3,
+                &mut session_rng(round_seed, "Practice"),
+            );
+            let qualifying = Qualifying::new("Qualifying").run(SessionInput {
+                world: &self.world,
+                entrants: &entrants,
+                circuit,
+                setups: &mut setups,
+                grid: &[],
+                rng: session_rng(round_seed, "Qualifying"),
+                events: &mut EventStream::disabled(),
+            })?;
+            let race = Race::after("Qualifying").run(SessionInput {
+                world: &self.world,
+                entrants: &entrants,
+                circuit,
+                setups: &mut setups,
+                grid: &qualifying.classification,
+                rng: session_rng(round_seed, "Race"),
+                events: &mut EventStream::disabled(),
+            })?;
+            if let Some(report) = race.race_report() {
+                reporter.race(&self.world, report);
+            }
+            let race_results = race.classification;
+
+            for (result, (driver, points)) in
+                race_results.iter().zip(championship_points(&race_results))
+            {
+                add_points(&mut driver_standings, driver, points);
+                add_points(&mut constructor_standings, result.team, points);
+
+                let stats = match driver_stats
+                    .iter_mut()
+                    .position(|stats| stats.driver == driver)
+                {
+                    Some(index) => &mut driver_stats[index],
+                    None => {
+                        driver_stats.push(DriverStats {
+                            driver,
+                            team: result.team,
+                            starts: 0,
+                            wins: 0,
+                            podiums: 0,
+                            points: 0,
+                            dnfs: 0,
+                        });
+                        driver_stats.last_mut().unwrap()
+                    }
+                };
+                stats.starts += 1;
+                stats.points += points;
+                if result.dnf {
+                    stats.dnfs += 1;
+                } else {
+                    stats.wins += (result.finish_position == 1) as u32;
+                    stats.podiums += (result.finish_position <= 3) as u32;
+                }
+            }
+
+            if let Some(winner) = race_results.first() {
+                reporter.note(&format!(
+                    "🏆 Winner: {} ({})",
+                    self.world.driver_name(winner.driver),
+                    self.world.team_name(winner.team)
+                ));
+            }
+            season_substitutions.extend(substitutions);
+        }
+
+        driver_standings.sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+        constructor_standings.sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+
+        let player_team_position = constructor_standings
+            .iter()
+            .position(|(team, _)| *team == self.player_team)
+            .map(|index| index + 1)
+            .unwrap_or(0);
+
+        let record = SeasonRecord {
+            season: self.season,
+            rounds: self.calendar.iter().map(|c| c.name.clone()).collect(),
+            driver_standings,
+            constructor_standings,
+            driver_stats,
+            substitutions: season_substitutions,
+            player_team_position,
+        };
+
+        self.history.push(record.clone());
+        Ok(record)
+    }
+
+    /// Moves the world on to the next season: prize money and salaries, car development,
+    /// the silly season, a new academy intake and calendar changes. Returns a log of what happened.
+    pub fn advance_season(
+        &mut self,
+        player_focus: DevelopmentFocus,
+        player_spend: f32,
+    ) -> Vec<String> {
+        let mut rng = rand::rng();
+        let mut log = Vec::new();
+        let Some(record) = self.history.last().cloned() else {
+            return log;
+        };
+
+        // Prize money by constructors' position, minus what was paid to the drivers
+        for (index, (team, _)) in record.constructor_standings.iter().enumerate() {
+            let prize_money = 140.0 - index as f32 * 10.0;
+            let salaries: f32 = self
+                .world
+                .team(*team)
+                .drivers
+                .iter()
+                .filter_map(|driver| self.world.driver(*driver).contract.as_ref())
+                .map(|contract| contract.salary)
+                .sum();
+
+            if let Some((_, balance)) = self.finances.iter_mut().find(|(id, _)| id == team) {
+                *balance += prize_money - salaries;
+                log.push(format!(
+                    "💰 {} earns ${:.1}M in prize money and pays ${:.1}M in salaries (balance ${:.1}M)",
+                    self.world.team_name(*team),
+                    prize_money,
+                    salaries,
+                    balance
+                ));
+            }
+        }
+
+        // Car development, the player picks the focus and spend, AI teams fix their weakest area
+        for team_id in self.world.team_ids() {
+            let (_, balance) = self
+                .finances
+                .iter_mut()
+                .find(|(id, _)| *id == team_id)
+                .expect("every team has a finances entry");
+            let car = self.world.car_mut(self.world.team(team_id).car);
+
+            let (focus, spend) = if team_id == self.player_team {
+                (player_focus, player_spend.clamp(0.0, balance.max(0.0)))
+            } else {
+                (
+                    DevelopmentFocus::weakest_area(car),
+                    balance.max(0.0) * rng.random_range(0.4..0.7),
+                )
+            };
+
+            // Everyone loses a little ground to the rules makers every winter
+            for attribute in [
+                &mut car.engine_power,
+                &mut car.tyre_management,
+                &mut car.reliability,
+                &mut car.aerodynamics,
+            ] {
+                *attribute = attribute.saturating_sub(rng.random_range(0..=1));
+            }
+
+            let gain = develop_car(car, focus, spend);
+            *balance -= spend;
+            let team = self.world.team_mut(team_id);
+            team.driver_budget = (*balance * 0.5).clamp(8.0, 80.0);
+
+            log.push(format!(
+                "🔧 {} spends ${:.1}M on {} (+{})",
+                team.name,
+                spend,
+                focus.label(),
+                gain
+            ));
+        }
+
+        // Drivers without a seat get older too and the oldest give up waiting
+        for driver in self.world.free_agents().to_vec() {
+            self.world.driver_mut(driver).age += 1;
+            if self.world.driver(driver).age > 35 {
+                self.world.retire(driver);
+            }
+        }
+
+        // A new crop of academy drivers joins the market. Numbers and acronyms of retired
+        // drivers are free to be used again.
+        let mut taken: Vec<Driver> = self
+            .world
+            .teams()
+            .iter()
+            .flat_map(|team| team.drivers.iter().chain(&team.reserve_drivers))
+            .chain(self.world.free_agents())
+            .map(|driver| self.world.driver(*driver).clone())
+            .collect();
+        for _ in 0..3 {
+            let graduate = academy_graduate(&taken);
+            log.push(format!(
+                "🎓 {} ({}) graduates from the junior ranks",
+                graduate.full_name, graduate.country
+            ));
+            taken.push(graduate.clone());
+            self.world.add_driver(graduate);
+        }
+
+        // Silly season based on last season's points
+        let moves = market::run_silly_season(&mut self.world, &record.driver_standings, &mut rng);
+        log.extend(moves.iter().map(|transfer| transfer.info(&self.world)));
+
+        // Now and then a team signs a new title sponsor and is renamed. Results and standings
+        // refer to the team by ID, so the history shows the new name too.
+        if rng.random_bool(0.2) {
+            let team = self.world.team_ids()[rng.random_range(0..self.world.teams().len())];
+            let sponsor = TITLE_SPONSORS[rng.random_range(0..TITLE_SPONSORS.len())];
+            let old_name = self.world.team_name(team).to_string();
+            let base_name = TITLE_SPONSORS
+                .iter()
+                .find_map(|sponsor| old_name.strip_prefix(&format!("{} ", sponsor)))
+                .unwrap_or(&old_name);
+            let new_name = format!("{} {}", sponsor, base_name);
+
+            if new_name != old_name {
+                log.push(format!(
+                    "🏷️ {} signs {} as title sponsor and becomes {}",
+                    old_name, sponsor, new_name
+                ));
+                self.world.rename_team(team, &new_name);
+            }
+        }
+
+        // The calendar changes a little every year
+        if !self.reserve_circuits.is_empty() && rng.random_bool(0.6) {
+            let dropped = self
+                .calendar
+                .remove(rng.random_range(0..self.calendar.len()));
+            let added = self
+                .reserve_circuits
+                .remove(rng.random_range(0..self.reserve_circuits.len()));
+            log.push(format!(
+                "📅 {} joins the calendar, replacing {}",
+                added.name, dropped.name
+            ));
+            self.calendar.push(added);
+            self.reserve_circuits.push(dropped);
+        }
+
+        self.season += 1;
+        log
+    }
+}
+
+/// Gives every race driver a small chance of missing the weekend through injury or illness,
+/// with the team's first reserve standing in.
+fn random_substitutions(world: &World) -> Vec<Substitution> {
+    let mut rng = rand::rng();
+    let mut substitutions = Vec::new();
+
+    for team in world.teams() {
+        let Some(reserve) = team.reserve_drivers.first() else {
+            continue;
+        };
+
+        for driver in &team.drivers {
+            if rng.random_bool(0.015) {
+                let reason = if rng.random_bool(0.5) {
+                    SubstitutionReason::Injury
+                } else {
+                    SubstitutionReason::Illness
+                };
+
+                substitutions.push(Substitution::new(
+                    team.id,
+                    *driver,
+                    *reserve,
+                    reason,
+                    SubstitutionScope::Weekend,
+                ));
+                break; // a team only has one reserve to call on
+            }
+        }
+    }
+
+    substitutions
+}
+
+/// Adds `points` to `entry` in a standings table, creating the entry if needed.
+pub fn add_points<T: PartialEq>(standings: &mut Vec<(T, u32)>, entry: T, points: u32) {
+    match standings.iter_mut().find(|(id, _)| *id == entry) {
+        Some((_, total)) => *total += points,
+        None => standings.push((entry, points)),
+    }
+}
+
+/// Spends money on one area of the car and returns how many points it gained.
+/// Every $25M buys roughly a point, with gains getting harder close to 100.
+fn develop_car(car: &mut Car, focus: DevelopmentFocus, spend: f32) -> u8 {
+    let attribute = match focus {
+        DevelopmentFocus::EnginePower => &mut car.engine_power,
+        DevelopmentFocus::TyreManagement => &mut car.tyre_management,
+        DevelopmentFocus::Reliability => &mut car.reliability,
+        DevelopmentFocus::Aerodynamics => &mut car.aerodynamics,
+    };
+
+    let headroom = (100 - *attribute) as f32 / 20.0;
+    let gain =
+        (spend / 25.0 * headroom.min(1.0) * rand::rng().random_range(0.5..1.5)).round() as u8;
+    let gain = gain.min(100 - *attribute);
+    *attribute += gain;
+
+    gain
+}
+
+/// Creates a young driver from the junior categories with a number and acronym not yet in use.
+fn academy_graduate(taken: &[Driver]) -> Driver {
+    let mut rng = rand::rng();
+    let mut graduate = generate_driver(&mut rng, taken, 80.0);
+    graduate.age = rng.random_range(18..22);
+    graduate.experience_level = rng.random_range(50..66);
+
+    graduate
+}
+
+/// Interactive career loop: loads `save_path` if it exists (or starts a new career from
+/// `roster_name`), then plays season after season, saving after each one. A save that exists
+/// but can't be loaded is an error, so it is never overwritten by a new career. Everything
+/// the player sees goes to `reporter`, and `ask` puts a question to them and hands back
+/// their answer.
+pub fn run_career(
+    save_path: &str,
+    roster_name: &str,
+    reporter: &mut dyn Reporter,
+    ask: &mut dyn FnMut(&str) -> String,
+) -> Result<(), Box<dyn Error>> {
+    let mut career = match Career::load(save_path) {
+        Ok(career) => {
+            reporter.note(&format!(
+                "📂 Loaded career from {} (season {})",
+                save_path, career.season
+            ));
+            career
+        }
+        Err(error)
+            if error
+                .downcast_ref::<io::Error>()
+                .is_some_and(|error| error.kind() == io::ErrorKind::NotFound) =>
+        {
+            let roster = Roster::load(roster_name)?;
+            reporter.note("\n🏁 New Career - choose your team 🏁");
+            for (index, team) in roster.teams.iter().enumerate() {
+                reporter.note(&format!("{}. {}", index + 1, team.name));
+            }
+
+            // Teams get their IDs in roster order
+            let choice: usize = ask("Team number:").parse().unwrap_or(1);
+            let team = TeamId((choice.clamp(1, roster.teams.len()) - 1) as u32);
+            Career::new(team, &roster)?
+        }
+        Err(error) => return Err(error),
+    };
+
+    loop {
+        reporter.note(&format!("\n🏁 Season {} 🏁", career.season));
+        reporter.note(&career.player_team().info(&career.world));
+        reporter.note(&format!(
+            "💰 Balance: ${:.1}M",
+            career.balance(career.player_team)
+        ));
+
+        let record = career.simulate_season(reporter)?;
+
+        reporter.note(&format!("\n🏁 {} Drivers' Championship 🏁", record.season));
+        for (position, (driver, points)) in record.driver_standings.iter().enumerate() {
+            match record
+                .driver_stats
+                .iter()
+                .find(|stats| stats.driver == *driver)
+            {
+                Some(stats) => {
+                    reporter.note(&format!("P{}: {}", position + 1, stats.info(&career.world)))
+                }
+                None => reporter.note(&format!(
+                    "P{}: {} - {} pts",
+                    position + 1,
+                    career.world.driver_name(*driver),
+                    points
+                )),
+            }
+        }
+        reporter.note(&format!(
+            "\n🏁 {} Constructors' Championship 🏁",
+            record.season
+        ));
+        for (position, (team, points)) in record.constructor_standings.iter().enumerate() {
+            reporter.note(&format!(
+                "P{}: {} - {} pts",
+                position + 1,
+                career.world.team_name(*team),
+                points
+            ));
+        }
+
+        reporter.note("\n🏁 Development 🏁");
+        reporter.note("1. Engine Power\n2. Tyre Management\n3. Reliability\n4. Aerodynamics");
+        let focus = match ask("Development focus for next season:").as_str() {
+            "1" => DevelopmentFocus::EnginePower,
+            "2" => DevelopmentFocus::TyreManagement,
+            "3" => DevelopmentFocus::Reliability,
+            _ => DevelopmentFocus::Aerodynamics,
+        };
+        let balance = career.balance(career.player_team);
+        let spend = ask(&format!(
+            "Amount to spend in $M (balance ${:.1}M):",
+            balance
+        ))
+        .parse()
+        .unwrap_or(balance / 2.0);
+
+        reporter.note("\n🏁 Off-season 🏁");
+        for entry in career.advance_season(focus, spend) {
+            reporter.note(&entry);
+        }
+
+        career.save(save_path)?;
+        reporter.note(&format!("\n💾 Career saved to {}", save_path));
+
+        reporter.note("\n🏁 Career History 🏁");
+        for record in &career.history {
+            reporter.note(&record.info(&career.world));
+        }
+
+        if !ask("Continue to next season? [y/N]").eq_ignore_ascii_case("y") {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roster::DEFAULT_ROSTER;
+
+    /// Saves `career` after `corrupt` has been at it, and loads it back.
+    fn reload(name: &str, corrupt: impl FnOnce(&mut Career)) -> Result<Career, Box<dyn Error>> {
+        let roster = Roster::load(DEFAULT_ROSTER).unwrap();
+        let mut career = Career::new(TeamId(0), &roster).unwrap();
+        corrupt(&mut career);
+
+        let path = std::env::temp_dir().join(format!("f1-career-{}.json", name));
+        let path = path.to_str().unwrap();
+        career.save(path).unwrap();
+        let loaded = Career::load(path);
+        fs::remove_file(path).ok();
+
+        loaded
+    }
+
+    #[test]
+    fn a_saved_career_loads_back() {
+        assert!(reload("intact", |_| {}).is_ok());
+    }
+
+    #[test]
+    fn corrupted_saves_are_rejected() {
+        let error = reload("unknown-player-team", |career| {
+            career.player_team = TeamId(99)
+        })
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("unknown team #99"), "{}", error);
+
+        let error = reload("missing-balance", |career| career.finances.truncate(1))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("0 bank balances"), "{}", error);
+
+        let error = reload("double-balance", |career| {
+            career.finances.push(career.finances[0])
+        })
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("2 bank balances"), "{}", error);
+
+        let error = reload("unknown-balance", |career| {
+            career.finances.push((TeamId(99), 10.0))
+        })
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("unknown team #99"), "{}", error);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::export::WeekendResults;
+use crate::models::{DriverId, RaceResult};
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const WIDTH: f32 = 960.0;
+const MARGIN_LEFT: f32 = 60.0;
+const MARGIN_RIGHT: f32 = 60.0;
+const MARGIN_TOP: f32 = 40.0;
+const MARGIN_BOTTOM: f32 = 40.0;
+const ROW_HEIGHT: f32 = 24.0;
+
+/// Position of every driver at the end of every lap. Teammates share their team's colour, the
+/// second driver of a team is drawn dashed.
+pub fn lap_chart_svg(weekend: &WeekendResults) -> String {
+    let race = &weekend.race;
+    let drivers = race.results.len().max(1);
+    let laps = race
+        .laps
+        .iter()
+        .map(|lap| lap.lap)
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let height = MARGIN_TOP + MARGIN_BOTTOM + ROW_HEIGHT * (drivers - 1) as f32;
+    let x = |lap: u8| MARGIN_LEFT + (WIDTH - MARGIN_LEFT - MARGIN_RIGHT) * lap as f32 / laps as f32;
+    let y = |position: u8| MARGIN_TOP + ROW_HEIGHT * (position as f32 - 1.0);
+
+    let mut svg = open_svg(height, &format!("{} - Lap Chart", weekend.circuit));
+    lap_axis(&mut svg, laps, height, &x);
+
+    for result in &race.results {
+        let laps = race.laps_of(result.driver);
+        let points: Vec<String> = laps
+            .iter()
+            .map(|lap| format!("{:.1},{:.1}", x(lap.lap), y(lap.position)))
+            .collect();
+        let (colour, dashed) = driver_style(weekend, result);
+        let acronym = acronym(weekend, result.driver);
+
+        let _ = writeln!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"#{}\" stroke-width=\"2.5\"{}/>",
+            points.join(" "),
+            colour,
+            dashed
+        );
+        if let (Some(first), Some(last)) = (laps.first(), laps.last()) {
+            let _ = writeln!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\" dy=\"4\">{}</text>",
+                x(first.lap) - 8.0,
+                y(first.position),
+                acronym
+            );
+            let _ = writeln!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" dy=\"4\">{}{}</text>",
+                x(last.lap) + 8.0,
+                y(last.position),
+                acronym,
+                if result.dnf { " ✕" } else { "" }
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Seconds behind the leader at the end of every lap, the leader being the flat line on top.
+pub fn gap_chart_svg(weekend: &WeekendResults) -> String {
+    let race = &weekend.race;
+    let laps = race
+        .laps
+        .iter()
+        .map(|lap| lap.lap)
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let gaps: Vec<(&RaceResult, Vec<(u8, f32)>)> = race
+        .results
+        .iter()
+        .map(|result| (result, race.gaps_to_leader(result.driver)))
+        .collect();
+    let max_gap = gaps
+        .iter()
+        .flat_map(|(_, gaps)| gaps.iter().map(|(_, gap)| *gap))
+        .fold(1.0, f32::max);
+
+    let height = 520.0;
+    let x = |lap: u8| MARGIN_LEFT + (WIDTH - MARGIN_LEFT - MARGIN_RIGHT) * lap as f32 / laps as f32;
+    let y = |gap: f32| MARGIN_TOP + (height - MARGIN_TOP - MARGIN_BOTTOM) * gap / max_gap;
+
+    let mut svg = open_svg(height, &format!("{} - Gap to Leader", weekend.circuit));
+    lap_axis(&mut svg, laps, height, &x);
+
+    // Horizontal grid lines every 10 or 30 seconds, depending on the spread
+    let step = if max_gap > 120.0 { 30.0 } else { 10.0 };
+    let mut gap = 0.0;
+    while gap <= max_gap {
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#e5e5ea\"/>\n\
+             <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\" dy=\"4\" fill=\"#6e6e73\">+{}s</text>",
+            MARGIN_LEFT,
+            y(gap),
+            WIDTH - MARGIN_RIGHT,
+            y(gap),
+            MARGIN_LEFT - 6.0,
+            y(gap),
+            gap
+        );
+        gap += step;
+    }
+
+    for (result, gaps) in &gaps {
+        let points: Vec<String> = gaps
+            .iter()
+            .map(|(lap, gap)| format!("{:.1},{:.1}", x(*lap), y(*gap)))
+            .collect();
+        let (colour, dashed) = driver_style(weekend, result);
+
+        let _ = writeln!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"#{}\" stroke-width=\"2\"{}/>",
+            points.join(" "),
+            colour,
+            dashed
+        );
+        if let Some((lap, gap)) = gaps.last() {
+            let _ = writeln!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" dy=\"4\">{}</text>",
+                x(*lap) + 6.0,
+                y(*gap),
+                acronym(weekend, result.driver)
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// One bar per driver in finishing order, split into stints in the compound's colour, with the
+/// team colour marking each row.
+pub fn tyre_strategy_svg(weekend: &WeekendResults) -> String {
+    let race = &weekend.race;
+    let laps = race
+        .laps
+        .iter()
+        .map(|lap| lap.lap)
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let height = MARGIN_TOP + MARGIN_BOTTOM + ROW_HEIGHT * race.results.len() as f32;
+    let x = |lap: f32| MARGIN_LEFT + (WIDTH - MARGIN_LEFT - MARGIN_RIGHT) * lap / laps as f32;
+
+    let mut svg = open_svg(height, &format!("{} - Tyre Strategy", weekend.circuit));
+    lap_axis(&mut svg, laps, height, &|lap| x(lap as f32));
+
+    for (row, result) in race.results.iter().enumerate() {
+        let top = MARGIN_TOP + ROW_HEIGHT * row as f32;
+        let (colour, _) = driver_style(weekend, result);
+
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"6\" height=\"{:.1}\" fill=\"#{}\"/>\n\
+             <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\" dy=\"4\">{}</text>",
+            MARGIN_LEFT - 52.0,
+            top + 3.0,
+            ROW_HEIGHT - 6.0,
+            colour,
+            MARGIN_LEFT - 8.0,
+            top + ROW_HEIGHT / 2.0,
+            acronym(weekend, result.driver)
+        );
+
+        for stint in race.stints(result.driver) {
+            let start = x(stint.first_lap as f32 - 1.0);
+            let end = x(stint.last_lap as f32);
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"4\" \
+                 fill=\"#{}\" stroke=\"#ffffff\" stroke-width=\"1.5\"/>\n\
+                 <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dy=\"4\">{}</text>",
+                start,
+                top + 3.0,
+                end - start,
+                ROW_HEIGHT - 6.0,
+                stint.compound.colour(),
+                (start + end) / 2.0,
+                top + ROW_HEIGHT / 2.0,
+                stint.compound.short_name()
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Writes the lap chart, gap chart and tyre strategy chart to `dir` and returns their paths.
+pub fn write_charts(dir: &Path, weekend: &WeekendResults) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+
+    let charts = [
+        ("lap_chart.svg", lap_chart_svg(weekend)),
+        ("gap_to_leader.svg", gap_chart_svg(weekend)),
+        ("tyre_strategy.svg", tyre_strategy_svg(weekend)),
+    ];
+
+    let mut written = Vec::new();
+    for (file_name, svg) in charts {
+        let path = dir.join(file_name);
+        fs::write(&path, svg)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+fn open_svg(height: f32, title: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\" font-family=\"Helvetica, Arial, sans-serif\" font-size=\"11\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n\
+         <text x=\"{x}\" y=\"22\" font-size=\"15\" font-weight=\"bold\">{title}</text>\n",
+        w = WIDTH,
+        h = height,
+        x = MARGIN_LEFT,
+        title = title.replace('&', "&amp;").replace('<', "&lt;")
+    )
+}
+
+/// Lap numbers along the bottom with a faint line every ten laps.
+fn lap_axis(svg: &mut String, laps: u8, height: f32, x: &dyn Fn(u8) -> f32) {
+    for lap in (0..laps).step_by(10).chain([laps]) {
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{x:.1}\" y1=\"{top:.1}\" x2=\"{x:.1}\" y2=\"{bottom:.1}\" stroke=\"#e5e5ea\"/>\n\
+             <text x=\"{x:.1}\" y=\"{label:.1}\" text-anchor=\"middle\" fill=\"#6e6e73\">{lap}</text>",
+            x = x(lap),
+            top = MARGIN_TOP - 10.0,
+            bottom = height - MARGIN_BOTTOM + 10.0,
+            label = height - MARGIN_BOTTOM + 24.0,
+            lap = lap
+        );
+    }
+}
+
+/// The team's colour, and a dash pattern for the team's second driver so teammates can be
+/// told apart.
+fn driver_style(weekend: &WeekendResults, result: &RaceResult) -> (String, &'static str) {
+    let team = weekend.world.team(result.team);
+    let second_driver = weekend
+        .race
+        .results
+        .iter()
+        .filter(|other| other.team == result.team)
+        .position(|other| other.driver == result.driver)
+        .unwrap_or(0)
+        > 0;
+
+    (
+        team.team_color.clone(),
+        if second_driver {
+            " stroke-dasharray=\"6 3\""
+        } else {
+            ""
+        },
+    )
+}
+
+fn acronym(weekend: &WeekendResults, driver: DriverId) -> String {
+    weekend.world.driver(driver).name_acronym.clone()
+}
@@ -0,0 +1,281 @@
+use crate::error::SimulationError;
+use crate::events::{EventStream, session_start};
+use crate::models::{
+    Circuit, DriverId, EngineMode, Flag, RaceEvent, RaceReport, TeamId, TeamOrderKind, TyreCompound,
+};
+use crate::race::RaceSimulation;
+use crate::render::{Cell, Table};
+use crate::replay::{Replay, describe_event};
+use crate::reporter::Reporter;
+use crate::session::session_rng;
+use crate::simulator::RaceEntry;
+use crate::world::World;
+
+const HELP: &str = "\
+Calls, naming a car by its acronym or as 1 or 2:
+  Enter                       run the next lap
+  box VER [soft|medium|hard]  pit at the end of the next lap (hards by default)
+  stay VER                    skip the next planned stop
+  mode VER push|standard|conserve
+                              push is quicker but harder on the engine
+  hold VER                    VER holds position behind their teammate
+  swap VER                    VER lets their teammate through
+  run N                       run N laps, stopping early for a safety car or retirement
+  finish                      run to the flag without stopping
+  help                        show this list";
+
+/// What the player asked for at the prompt between laps.
+enum Call {
+    Laps(u8), // laps to run before asking again
+    Finish,
+    Box(DriverId, TyreCompound),
+    StayOut(DriverId),
+    Mode(DriverId, EngineMode),
+    Order(DriverId, TeamOrderKind),
+    Help,
+}
+
+/// Runs the race with the player on `team`'s pit wall. Between laps the timing tower and what
+/// happened on track are shown, and the player calls stops, engine modes and team orders for
+/// their two cars. Every call goes into the event stream, so it ends up in the replay too.
+/// What the player sees goes to `reporter`, and `ask` puts a question to them and hands back
+/// their answer.
+#[allow(clippy::too_many_arguments)]
+pub fn run_engineer(
+    world: &World,
+    circuit: &Circuit,
+    entry: &RaceEntry,
+    seed: u64,
+    team: TeamId,
+    events: &mut EventStream,
+    reporter: &mut dyn Reporter,
+    ask: &mut dyn FnMut(&str) -> String,
+) -> Result<RaceReport, SimulationError> {
+    let mut race = RaceSimulation::new(
+        world,
+        &entry.drivers,
+        circuit,
+        &entry.setups,
+        &entry.grid,
+        session_rng(seed, "Race"),
+    )?;
+    race.take_pit_wall(team);
+
+    // The tower is drawn from the events so far, the same way a replay draws it
+    let start = session_start(
+        world,
+        "Race",
+        &circuit.name,
+        Some(circuit.laps),
+        &entry.drivers,
+    );
+    events.emit(&start);
+    let mut history = Replay::of_race(seed, &entry.grid, start);
+    let lap_events = race.take_events();
+    events.emit_all(lap_events.iter().cloned());
+    history.events.extend(lap_events);
+
+    reporter.note(&format!(
+        "\n🎧 You're on the {} pit wall. Type `help` for the calls you can make.",
+        world.team_name(team)
+    ));
+    let mut laps_to_run = 0;
+    let mut to_the_flag = false;
+    while !race.is_finished() {
+        if laps_to_run == 0 && !to_the_flag {
+            show_pit_wall(world, &race, team, &history, reporter);
+            match take_calls(world, &mut race, team, reporter, ask) {
+                Some(laps) => laps_to_run = laps,
+                None => to_the_flag = true,
+            }
+        }
+
+        race.simulate_lap();
+        laps_to_run = laps_to_run.saturating_sub(1);
+        let lap_events = race.take_events();
+        if laps_to_run > 0 || to_the_flag {
+            for line in lap_events
+                .iter()
+                .filter_map(|event| describe_event(event, &|driver| acronym(world, driver)))
+            {
+                reporter.note(&format!("L{:<3} {}", race.lap, line));
+            }
+        }
+
+        // A safety car or losing a car is worth stopping a longer run for, unless the player
+        // asked to go straight to the flag
+        let needs_a_call = lap_events.iter().any(|event| match event {
+            RaceEvent::Flag { flag, .. } => *flag == Flag::SafetyCar,
+            RaceEvent::Retirement { driver, .. } => world.team_of(*driver).ok() == Some(team),
+            _ => false,
+        });
+        if needs_a_call {
+            laps_to_run = 0;
+        }
+
+        events.emit_all(lap_events.iter().cloned());
+        history.events.extend(lap_events);
+    }
+
+    let report = race.into_report();
+    events.emit(&RaceEvent::SessionEnd {
+        session: "Race".to_string(),
+        classification: report.results.clone(),
+    });
+
+    Ok(report)
+}
+
+fn acronym(world: &World, driver: &DriverId) -> String {
+    world.driver(*driver).name_acronym.clone()
+}
+
+/// The lap counter, timing tower, last lap's race control messages and the state of the
+/// player's cars.
+fn show_pit_wall(
+    world: &World,
+    race: &RaceSimulation,
+    team: TeamId,
+    history: &Replay,
+    reporter: &mut dyn Reporter,
+) {
+    history.show(race.lap, reporter);
+    if race.safety_car_out() {
+        reporter.note("🚨 Safety car on track");
+    }
+
+    let mut table = Table::new(&["", "Car", "Tyre", "Mode", "Next Stop"]);
+    for race_car in race.cars.iter().filter(|race_car| race_car.team == team) {
+        let colour = &world.team(team).team_color;
+        let next_stop = match (race_car.retired, race_car.planned_stops.first()) {
+            (true, _) => "Retired".to_string(),
+            (false, Some((lap, compound))) => format!("Lap {} for {}", lap, compound.name()),
+            (false, None) => "None".to_string(),
+        };
+        table.row(vec![
+            Cell::bar(colour),
+            Cell::team(race_car.driver.name_acronym.clone(), colour),
+            Cell::coloured(
+                format!(
+                    "{} {:>2}",
+                    race_car.compound.short_name(),
+                    race_car.tyre_age
+                ),
+                race_car.compound.colour(),
+            ),
+            Cell::new(race_car.engine_mode.name()),
+            Cell::new(next_stop),
+        ]);
+    }
+    reporter.table(&format!("🎧 {} Pit Wall", world.team_name(team)), &table);
+}
+
+/// Asks for calls until the player wants laps run, and returns how many, or `None` to run to
+/// the flag.
+fn take_calls(
+    world: &World,
+    race: &mut RaceSimulation,
+    team: TeamId,
+    reporter: &mut dyn Reporter,
+    ask: &mut dyn FnMut(&str) -> String,
+) -> Option<u8> {
+    loop {
+        let answer = ask("🎧 Call (Enter for the next lap):");
+        let call = match parse_call(&answer, race, team) {
+            Ok(call) => call,
+            Err(message) => {
+                reporter.note(&format!("❓ {}", message));
+                continue;
+            }
+        };
+
+        let name = |driver: DriverId| world.driver_name(driver).to_string();
+        match call {
+            Call::Laps(laps) => return Some(laps),
+            Call::Finish => return None,
+            Call::Help => reporter.note(HELP),
+            Call::Box(driver, compound) => {
+                if race.box_driver(driver, compound) {
+                    reporter.note(&format!(
+                        "📻 {}, box this lap for {}",
+                        name(driver),
+                        compound.name()
+                    ));
+                }
+            }
+            Call::StayOut(driver) => {
+                if race.stay_out(driver) {
+                    reporter.note(&format!("📻 {}, stay out", name(driver)));
+                } else {
+                    reporter.note(&format!("❓ {} has no stop planned", name(driver)));
+                }
+            }
+            Call::Mode(driver, mode) => {
+                if race.set_engine_mode(driver, mode) {
+                    reporter.note(&format!("📻 {}, engine mode {}", name(driver), mode.name()));
+                }
+            }
+            Call::Order(driver, kind) => match race.give_team_order(driver, kind) {
+                Some(order) => reporter.note(&order.info(world)),
+                None => reporter.note("❓ Team orders need both cars running"),
+            },
+        }
+    }
+}
+
+fn parse_call(answer: &str, race: &RaceSimulation, team: TeamId) -> Result<Call, String> {
+    let words: Vec<String> = answer.split_whitespace().map(str::to_lowercase).collect();
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    let driver = |name: &str| find_car(race, team, name);
+
+    match words.as_slice() {
+        [] => Ok(Call::Laps(1)),
+        ["help"] => Ok(Call::Help),
+        ["finish"] => Ok(Call::Finish),
+        ["run", laps] => match laps.parse::<u8>() {
+            Ok(laps) if laps > 0 => Ok(Call::Laps(laps)),
+            _ => Err(format!("`{}` is not a number of laps", laps)),
+        },
+        ["box", car] => Ok(Call::Box(driver(car)?, TyreCompound::Hard)),
+        ["box", car, compound] => {
+            let compound = TyreCompound::from_name(compound)
+                .ok_or_else(|| format!("unknown compound `{}`", compound))?;
+            Ok(Call::Box(driver(car)?, compound))
+        }
+        ["stay", car] => Ok(Call::StayOut(driver(car)?)),
+        ["mode", car, mode] => {
+            let mode = EngineMode::from_name(mode)
+                .ok_or_else(|| format!("unknown engine mode `{}`", mode))?;
+            Ok(Call::Mode(driver(car)?, mode))
+        }
+        ["hold", car] => Ok(Call::Order(driver(car)?, TeamOrderKind::HoldPosition)),
+        ["swap", car] => Ok(Call::Order(driver(car)?, TeamOrderKind::LetTeammateThrough)),
+        _ => Err(format!(
+            "unknown call `{}`, type `help` for the list",
+            answer
+        )),
+    }
+}
+
+/// One of the player's cars still in the race, by acronym or as car 1 or 2.
+fn find_car(race: &RaceSimulation, team: TeamId, name: &str) -> Result<DriverId, String> {
+    let cars: Vec<_> = race
+        .cars
+        .iter()
+        .filter(|race_car| race_car.team == team)
+        .collect();
+    let race_car = match name {
+        "1" => cars.first(),
+        "2" => cars.get(1),
+        _ => cars
+            .iter()
+            .find(|race_car| race_car.driver.name_acronym.eq_ignore_ascii_case(name)),
+    }
+    .ok_or_else(|| format!("`{}` is not one of your cars", name))?;
+
+    if race_car.retired {
+        return Err(format!("{} has retired", race_car.driver.name_acronym));
+    }
+
+    Ok(race_car.driver.id)
+}
@@ -0,0 +1,156 @@
+use std::error::Error;
+use std::fmt;
+
+/// A single problem found while validating a roster.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RosterProblem {
+    /// A driver (race driver, reserve or rookie) belongs to a team the roster doesn't define.
+    UnknownTeam { driver: String, team: String },
+    /// A team doesn't have exactly two race drivers.
+    WrongDriverCount { team: String, count: usize },
+    /// Two drivers share the same race number.
+    DuplicateNumber {
+        number: u8,
+        first: String,
+        second: String,
+    },
+    /// Two drivers share the same three-letter acronym.
+    DuplicateAcronym {
+        acronym: String,
+        first: String,
+        second: String,
+    },
+    /// A driver or car attribute is above 100.
+    AttributeOutOfRange {
+        owner: String,
+        attribute: &'static str,
+        value: u8,
+    },
+    /// A team colour that isn't a six digit hex code like `FF8700`.
+    InvalidColour { team: String, colour: String },
+    /// A car belongs to a team the roster doesn't define.
+    CarWithoutTeam { car: String, team: String },
+    /// A team has no car.
+    MissingCar { team: String },
+    /// A team's number one driver doesn't race for the team.
+    NumberOneNotInTeam { team: String, driver: String },
+    /// A team or the free-agent market refers to a driver ID that doesn't exist, which only
+    /// happens in a hand-edited save.
+    UnknownDriver { team: String, id: u32 },
+}
+
+impl fmt::Display for RosterProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RosterProblem::UnknownTeam { driver, team } => {
+                write!(f, "{} drives for unknown team {}", driver, team)
+            }
+            RosterProblem::WrongDriverCount { team, count } => {
+                write!(f, "{} has {} race drivers instead of 2", team, count)
+            }
+            RosterProblem::DuplicateNumber {
+                number,
+                first,
+                second,
+            } => write!(f, "{} and {} both use number {}", first, second, number),
+            RosterProblem::DuplicateAcronym {
+                acronym,
+                first,
+                second,
+            } => write!(f, "{} and {} both use acronym {}", first, second, acronym),
+            RosterProblem::AttributeOutOfRange {
+                owner,
+                attribute,
+                value,
+            } => write!(f, "{} has {} {} (maximum is 100)", owner, attribute, value),
+            RosterProblem::InvalidColour { team, colour } => {
+                write!(f, "{} has invalid colour \"{}\"", team, colour)
+            }
+            RosterProblem::CarWithoutTeam { car, team } => {
+                write!(f, "car {} belongs to unknown team {}", car, team)
+            }
+            RosterProblem::MissingCar { team } => write!(f, "{} has no car", team),
+            RosterProblem::NumberOneNotInTeam { team, driver } => {
+                write!(
+                    f,
+                    "{}'s number one driver {} doesn't race for them",
+                    team, driver
+                )
+            }
+            RosterProblem::UnknownDriver { team, id } => {
+                write!(f, "{} refers to unknown driver #{}", team, id)
+            }
+        }
+    }
+}
+
+/// Why a roster couldn't be loaded.
+#[derive(Debug)]
+pub enum RosterError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    /// The roster parsed but failed validation, with every problem found.
+    Invalid(Vec<RosterProblem>),
+}
+
+impl fmt::Display for RosterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RosterError::Io(error) => write!(f, "could not read roster: {}", error),
+            RosterError::Parse(error) => write!(f, "could not parse roster: {}", error),
+            RosterError::Invalid(problems) => {
+                write!(f, "roster has {} problem(s)", problems.len())?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for RosterError {}
+
+impl From<std::io::Error> for RosterError {
+    fn from(error: std::io::Error) -> Self {
+        RosterError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for RosterError {
+    fn from(error: serde_json::Error) -> Self {
+        RosterError::Parse(error)
+    }
+}
+
+/// Why a session couldn't be simulated.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulationError {
+    /// A driver was entered into a session without a team, so there is no car for them.
+    NoTeam { driver: String },
+    /// A what-if fork that can't be run, like a pit stop on a lap that has already happened.
+    InvalidFork { reason: String },
+    /// A substitution that can't happen, like a driver standing in for another team.
+    InvalidSubstitution { reason: String },
+    /// A playback speed that isn't a positive number of times real time.
+    InvalidSpeed { speed: f32 },
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SimulationError::NoTeam { driver } => {
+                write!(f, "{} has no team and so no car to drive", driver)
+            }
+            SimulationError::InvalidFork { reason } => write!(f, "cannot fork race: {}", reason),
+            SimulationError::InvalidSubstitution { reason } => {
+                write!(f, "cannot substitute: {}", reason)
+            }
+            SimulationError::InvalidSpeed { speed } => {
+                write!(f, "speed must be above 0x, not {}x", speed)
+            }
+        }
+    }
+}
+
+impl Error for SimulationError {}
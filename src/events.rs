@@ -0,0 +1,114 @@
+use crate::models::{DriverId, Entrant, RaceEvent};
+use crate::world::World;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// Writes simulation events as newline-delimited JSON, one event per line, flushed as soon as
+/// it happens so tools like `jq` or a socket reader can follow the session live.
+pub struct EventStream {
+    writer: Option<Box<dyn Write>>,
+    recording: Option<Vec<RaceEvent>>, // kept for a replay file
+    error: Option<io::Error>,          // why the writer was switched off
+}
+
+impl EventStream {
+    /// A stream that throws every event away, for when nobody is listening.
+    pub fn disabled() -> EventStream {
+        EventStream {
+            writer: None,
+            recording: None,
+            error: None,
+        }
+    }
+
+    pub fn stdout() -> EventStream {
+        EventStream {
+            writer: Some(Box::new(io::stdout())),
+            recording: None,
+            error: None,
+        }
+    }
+
+    pub fn to_file(path: &str) -> io::Result<EventStream> {
+        Ok(EventStream {
+            writer: Some(Box::new(BufWriter::new(File::create(path)?))),
+            recording: None,
+            error: None,
+        })
+    }
+
+    /// Keeps a copy of every event from now on, on top of writing it out.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    /// Every event kept since `start_recording`.
+    pub fn take_recording(&mut self) -> Vec<RaceEvent> {
+        self.recording.take().unwrap_or_default()
+    }
+
+    /// Why the stream was switched off, if the reader went away.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Writes one event. If the reader goes away the stream is switched off rather than
+    /// stopping the simulation, and the error is kept for `take_error`.
+    pub fn emit(&mut self, event: &RaceEvent) {
+        if let Some(recording) = self.recording.as_mut() {
+            recording.push(event.clone());
+        }
+
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+
+        let written = serde_json::to_writer(&mut *writer, event)
+            .map_err(io::Error::from)
+            .and_then(|_| writer.write_all(b"\n"))
+            .and_then(|_| writer.flush());
+
+        if let Err(error) = written {
+            self.error = Some(error);
+            self.writer = None;
+        }
+    }
+
+    pub fn emit_all(&mut self, events: impl IntoIterator<Item = RaceEvent>) {
+        for event in events {
+            self.emit(&event);
+        }
+    }
+}
+
+/// The event opening a session, with every entrant so IDs in later events can be resolved.
+/// Drivers without a team can't take part and are left out.
+pub fn session_start(
+    world: &World,
+    session: &str,
+    circuit: &str,
+    laps: Option<u8>,
+    drivers: &[DriverId],
+) -> RaceEvent {
+    RaceEvent::SessionStart {
+        session: session.to_string(),
+        circuit: circuit.to_string(),
+        laps,
+        entrants: drivers
+            .iter()
+            .filter_map(|&id| {
+                let driver = world.driver(id);
+                let team = driver.team?;
+
+                Some(Entrant {
+                    driver: id,
+                    name: driver.full_name.clone(),
+                    acronym: driver.name_acronym.clone(),
+                    team,
+                    team_name: world.team_name(team).to_string(),
+                    team_color: world.team(team).team_color.clone(),
+                })
+            })
+            .collect(),
+    }
+}
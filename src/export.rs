@@ -0,0 +1,151 @@
+use crate::fork::ForkOutcome;
+use crate::models::{DriverId, PracticeSession, RaceReport, RaceResult, TeamId};
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Every session of a race weekend, as it came out of the simulation, with the world as it was
+/// that weekend so the IDs in the results can be resolved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeekendResults {
+    pub world: World,
+    pub seed: u64, // of the race
+    pub circuit: String,
+    pub practice: Vec<PracticeSession>,
+    pub qualifying: Vec<RaceResult>,
+    pub race: RaceReport,
+    #[serde(default)]
+    pub fork: Option<ForkOutcome>, // when the weekend was run with a what-if fork
+}
+
+/// One line of a session classification. Names are resolved so the file can be used on its
+/// own, the IDs are kept so rows can be joined across sessions even if a team is renamed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassificationRow {
+    pub session: String,
+    pub position: u8,
+    pub driver_id: DriverId,
+    pub driver: String,
+    pub acronym: String,
+    pub team_id: TeamId,
+    pub team: String,
+    pub time_seconds: f32,
+    pub dnf: bool,
+}
+
+/// A single practice lap, one row per lap so lap times can be plotted straight away.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PracticeLapRow {
+    pub session: String,
+    pub driver_id: DriverId,
+    pub driver: String,
+    pub team_id: TeamId,
+    pub team: String,
+    pub run: usize,
+    pub run_type: String,
+    pub compound: String,
+    pub lap: usize,
+    pub lap_time_seconds: f32,
+}
+
+pub fn classification_rows(
+    world: &World,
+    session: &str,
+    results: &[RaceResult],
+) -> Vec<ClassificationRow> {
+    results
+        .iter()
+        .map(|result| {
+            let driver = world.driver(result.driver);
+
+            ClassificationRow {
+                session: session.to_string(),
+                position: result.finish_position,
+                driver_id: result.driver,
+                driver: driver.full_name.clone(),
+                acronym: driver.name_acronym.clone(),
+                team_id: result.team,
+                team: world.team_name(result.team).to_string(),
+                time_seconds: result.finish_time_seconds,
+                dnf: result.dnf,
+            }
+        })
+        .collect()
+}
+
+pub fn practice_lap_rows(world: &World, sessions: &[PracticeSession]) -> Vec<PracticeLapRow> {
+    let mut rows = Vec::new();
+
+    for session in sessions {
+        for (run_index, run) in session.runs.iter().enumerate() {
+            for (lap_index, lap_time) in run.lap_times.iter().enumerate() {
+                rows.push(PracticeLapRow {
+                    session: session.name.clone(),
+                    driver_id: run.driver,
+                    driver: world.driver_name(run.driver).to_string(),
+                    team_id: run.team,
+                    team: world.team_name(run.team).to_string(),
+                    run: run_index + 1,
+                    run_type: run.run_type.name(),
+                    compound: run.run_type.compound().name().to_string(),
+                    lap: lap_index + 1,
+                    lap_time_seconds: *lap_time,
+                });
+            }
+        }
+    }
+
+    rows
+}
+
+/// Writes every classification of the weekend plus all practice laps to `dir`, each as both
+/// a JSON and a CSV file, and the untouched weekend as `weekend.json`. Returns the paths written.
+pub fn export_weekend(
+    dir: &Path,
+    weekend: &WeekendResults,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    fs::create_dir_all(dir)?;
+    let world = &weekend.world;
+    let mut written = Vec::new();
+
+    let mut classifications: Vec<(String, &[RaceResult])> = weekend
+        .practice
+        .iter()
+        .map(|session| (session.name.clone(), session.results.as_slice()))
+        .collect();
+    classifications.push(("Qualifying".to_string(), &weekend.qualifying));
+    classifications.push(("Race".to_string(), &weekend.race.results));
+
+    for (session, results) in classifications {
+        let rows = classification_rows(world, &session, results);
+        let stem = session.to_lowercase();
+        written.push(write_json(&dir.join(format!("{}.json", stem)), &rows)?);
+        written.push(write_csv(&dir.join(format!("{}.csv", stem)), &rows)?);
+    }
+
+    let laps = practice_lap_rows(world, &weekend.practice);
+    written.push(write_json(&dir.join("practice_laps.json"), &laps)?);
+    written.push(write_csv(&dir.join("practice_laps.csv"), &laps)?);
+
+    written.push(write_json(&dir.join("weekend.json"), weekend)?);
+
+    Ok(written)
+}
+
+fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<PathBuf, Box<dyn Error>> {
+    fs::write(path, serde_json::to_string_pretty(value)?)?;
+
+    Ok(path.to_path_buf())
+}
+
+fn write_csv<T: Serialize>(path: &Path, rows: &[T]) -> Result<PathBuf, Box<dyn Error>> {
+    let mut writer = csv::Writer::from_path(path)?;
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+
+    Ok(path.to_path_buf())
+}
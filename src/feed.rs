@@ -0,0 +1,250 @@
+use crate::error::SimulationError;
+use crate::events::{EventStream, session_start};
+use crate::export::classification_rows;
+use crate::models::{Circuit, DriverId, RaceEvent, RaceReport};
+use crate::race::RaceSimulation;
+use crate::replay::{Replay, describe_event};
+use crate::reporter::Reporter;
+use crate::session::session_rng;
+use crate::simulator::RaceEntry;
+use crate::world::World;
+use serde_json::{Value, json};
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+use tungstenite::{Message, WebSocket};
+
+/// How long a client may hold up a message before it is dropped, so one stalled dashboard
+/// can't hold the race up for everyone else.
+const SEND_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long a new connection has to finish the WebSocket handshake before it is turned away.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Default)]
+struct Subscribers {
+    history: Vec<String>, // every message so far, sent first to clients joining late
+    clients: Vec<WebSocket<TcpStream>>,
+}
+
+/// Live timing of one race for any number of WebSocket clients. Every message is a JSON
+/// object with a `type`: `session`, `timing`, `race_control`, `pit` or `finish`.
+pub struct Feed {
+    address: SocketAddr,
+    subscribers: Arc<Mutex<Subscribers>>,
+}
+
+impl Feed {
+    /// Starts listening on `address`. Clients can connect at any time until the race is over
+    /// and are caught up on what they missed.
+    pub fn listen(address: &str) -> io::Result<Feed> {
+        let listener = TcpListener::bind(address)?;
+        let feed = Feed {
+            address: listener.local_addr()?,
+            subscribers: Arc::new(Mutex::new(Subscribers::default())),
+        };
+
+        let subscribers = Arc::clone(&feed.subscribers);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // Each handshake gets a thread of its own, so a client that connects and
+                // then says nothing can't keep the others out
+                let subscribers = Arc::clone(&subscribers);
+                thread::spawn(move || join(stream, &subscribers));
+            }
+        });
+
+        Ok(feed)
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn clients(&self) -> usize {
+        lock(&self.subscribers).clients.len()
+    }
+
+    /// Sends `message` to every client, dropping the ones that have gone away.
+    pub fn publish(&self, message: Value) {
+        let text = message.to_string();
+        let mut subscribers = lock(&self.subscribers);
+        subscribers
+            .clients
+            .retain_mut(|socket| socket.send(Message::text(text.as_str())).is_ok());
+        subscribers.history.push(text);
+    }
+
+    /// Says goodbye to every client once the race is over.
+    pub fn close(&self) {
+        for mut socket in lock(&self.subscribers).clients.drain(..) {
+            let _ = socket.close(None);
+            let _ = socket.flush();
+        }
+    }
+}
+
+/// Shakes hands with a new client and catches it up on the race so far. Anything that isn't a
+/// WebSocket handshake is turned away.
+fn join(stream: TcpStream, subscribers: &Mutex<Subscribers>) {
+    let _ = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(SEND_TIMEOUT));
+    let Ok(mut socket) = tungstenite::accept(stream) else {
+        return;
+    };
+
+    let mut subscribers = lock(subscribers);
+    let caught_up = subscribers
+        .history
+        .iter()
+        .all(|text| socket.send(Message::text(text.as_str())).is_ok());
+    if caught_up {
+        subscribers.clients.push(socket);
+    }
+}
+
+/// A client thread panicking mid-send leaves the list as usable as it was.
+fn lock(subscribers: &Mutex<Subscribers>) -> MutexGuard<'_, Subscribers> {
+    subscribers
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Runs the race from `grid`, publishing every lap to `feed` once it would have been run on
+/// track at `speed` times real time: race control messages and pit stops first, then the
+/// timing tower. Events also go to `events` as usual. Fails if `speed` isn't above zero.
+#[allow(clippy::too_many_arguments)]
+pub fn run_feed(
+    world: &World,
+    circuit: &Circuit,
+    entry: &RaceEntry,
+    seed: u64,
+    speed: f32,
+    feed: &Feed,
+    events: &mut EventStream,
+    reporter: &mut dyn Reporter,
+) -> Result<RaceReport, SimulationError> {
+    if !(speed.is_finite() && speed > 0.0) {
+        return Err(SimulationError::InvalidSpeed { speed });
+    }
+
+    let mut race = RaceSimulation::new(
+        world,
+        &entry.drivers,
+        circuit,
+        &entry.setups,
+        &entry.grid,
+        session_rng(seed, "Race"),
+    )?;
+    let start = session_start(
+        world,
+        "Race",
+        &circuit.name,
+        Some(circuit.laps),
+        &entry.drivers,
+    );
+    events.emit(&start);
+    if let RaceEvent::SessionStart { entrants, .. } = &start {
+        feed.publish(json!({
+            "type": "session",
+            "circuit": circuit.name,
+            "laps": circuit.laps,
+            "weather": circuit.weather.name(),
+            "speed": speed,
+            "entrants": entrants,
+            "grid": classification_rows(world, "Qualifying", &entry.grid),
+        }));
+    }
+
+    // The tower is worked out from the events, the same way a replay shows it
+    let mut history = Replay::of_race(seed, &entry.grid, start);
+    let lap_events = race.take_events();
+    events.emit_all(lap_events.iter().cloned());
+    history.events.extend(lap_events);
+    publish_timing(feed, &history, &race);
+
+    while !race.is_finished() {
+        let lap_started = Instant::now();
+        race.simulate_lap();
+        let lap_events = race.take_events();
+
+        // Hold the lap back until the leader would have finished it
+        let leader_lap = race
+            .running_order()
+            .first()
+            .map(|&index| race.cars[index].last_lap_time)
+            .unwrap_or_default();
+        thread::sleep(
+            Duration::from_secs_f32(leader_lap / speed).saturating_sub(lap_started.elapsed()),
+        );
+
+        for event in &lap_events {
+            publish_event(feed, world, event);
+        }
+        events.emit_all(lap_events.iter().cloned());
+        history.events.extend(lap_events);
+        publish_timing(feed, &history, &race);
+        reporter.note(&format!(
+            "📡 Lap {}/{} sent to {} clients",
+            race.lap,
+            circuit.laps,
+            feed.clients()
+        ));
+    }
+
+    let report = race.into_report();
+    events.emit(&RaceEvent::SessionEnd {
+        session: "Race".to_string(),
+        classification: report.results.clone(),
+    });
+    feed.publish(json!({
+        "type": "finish",
+        "classification": classification_rows(world, "Race", &report.results),
+    }));
+
+    Ok(report)
+}
+
+fn publish_timing(feed: &Feed, history: &Replay, race: &RaceSimulation) {
+    feed.publish(json!({
+        "type": "timing",
+        "lap": race.lap,
+        "laps": race.circuit.laps,
+        "safety_car": race.safety_car_out(),
+        "tower": history.timing_tower(race.lap),
+    }));
+}
+
+/// Pit stops get a message of their own, everything else worth a line on the race control
+/// feed goes out as a race control message with the event itself attached.
+fn publish_event(feed: &Feed, world: &World, event: &RaceEvent) {
+    if let RaceEvent::PitStop {
+        lap,
+        driver,
+        compound,
+        stop,
+    } = event
+    {
+        feed.publish(json!({
+            "type": "pit",
+            "lap": lap,
+            "driver_id": driver,
+            "acronym": world.driver(*driver).name_acronym,
+            "compound": compound,
+            "stop": stop,
+        }));
+        return;
+    }
+
+    let name = |driver: &DriverId| world.driver(*driver).name_acronym.clone();
+    if let Some(message) = describe_event(event, &name) {
+        feed.publish(json!({
+            "type": "race_control",
+            "lap": event.lap(),
+            "message": message,
+            "event": event,
+        }));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::error::SimulationError;
+use crate::models::{RaceReport, TyreCompound};
+use crate::race::RaceSimulation;
+use crate::world::World;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// A different pit stop call for one driver, written `VER:22:hard` on the command line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PitChange {
+    pub acronym: String,
+    pub lap: u8,
+    pub compound: TyreCompound,
+}
+
+impl PitChange {
+    pub fn parse(spec: &str) -> Option<PitChange> {
+        let mut parts = spec.split(':');
+        let acronym = parts.next()?.to_uppercase();
+        let lap = parts.next()?.parse().ok()?;
+        let compound = TyreCompound::from_name(parts.next()?)?;
+
+        Some(PitChange {
+            acronym,
+            lap,
+            compound,
+        })
+    }
+}
+
+/// Where to split the race off and what to do differently from there.
+/// Without a pit change or seed the fork just gets new luck.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkPlan {
+    pub lap: u8,
+    pub seed: Option<u64>,
+    pub pit: Option<PitChange>,
+}
+
+impl ForkPlan {
+    /// What the fork does differently, like "VER pits on lap 22 for Hard".
+    pub fn change(&self) -> String {
+        match &self.pit {
+            Some(pit) => format!(
+                "{} pits on lap {} for {}",
+                pit.acronym,
+                pit.lap,
+                pit.compound.name()
+            ),
+            None => "the race plays out with different luck".to_string(),
+        }
+    }
+}
+
+/// Both timelines of a forked race, run to the chequered flag, with the plan that split them.
+/// The plan's seed is the one the fork actually used.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkOutcome {
+    pub plan: ForkPlan,
+    pub original: RaceReport,
+    pub forked: RaceReport,
+}
+
+/// One finishing position in both timelines, with how far the original driver moved in the
+/// fork (positive is a gain).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkRow {
+    pub position: u8,
+    pub original: String,
+    pub fork: String,
+    pub driver: String,
+    pub change: i16,
+}
+
+impl ForkOutcome {
+    /// The original and forked classifications side by side, position by position.
+    pub fn rows(&self, world: &World) -> Vec<ForkRow> {
+        let describe = |driver, time: f32, dnf: bool| {
+            let acronym = &world.driver(driver).name_acronym;
+            if dnf {
+                format!("{} DNF", acronym)
+            } else {
+                format!("{} {:.3}s", acronym, time)
+            }
+        };
+
+        self.original
+            .results
+            .iter()
+            .zip(&self.forked.results)
+            .map(|(original, forked)| {
+                // How the driver in this original position did in the fork
+                let change = self
+                    .forked
+                    .results
+                    .iter()
+                    .find(|result| result.driver == original.driver)
+                    .map(|result| original.finish_position as i16 - result.finish_position as i16)
+                    .unwrap_or(0);
+
+                ForkRow {
+                    position: original.finish_position,
+                    original: describe(original.driver, original.finish_time_seconds, original.dnf),
+                    fork: describe(forked.driver, forked.finish_time_seconds, forked.dnf),
+                    driver: world.driver(original.driver).name_acronym.clone(),
+                    change,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Runs `race` to the fork lap, snapshots it and plays out both timelines from there. A fork
+/// without a pit change or seed draws its new luck from `rng`.
+pub fn run_fork(
+    world: &World,
+    mut race: RaceSimulation,
+    plan: &ForkPlan,
+    rng: &mut impl Rng,
+) -> Result<ForkOutcome, SimulationError> {
+    while race.lap < plan.lap && !race.is_finished() {
+        race.simulate_lap();
+    }
+
+    let mut plan = plan.clone();
+    if plan.pit.is_none() && plan.seed.is_none() {
+        plan.seed = Some(rng.random());
+    }
+    let mut forked = race.fork(plan.seed);
+
+    if let Some(pit) = &plan.pit {
+        let driver = forked
+            .cars
+            .iter()
+            .map(|race_car| race_car.driver.id)
+            .find(|&id| world.driver(id).name_acronym == pit.acronym)
+            .ok_or_else(|| SimulationError::InvalidFork {
+                reason: format!("no driver {} in the race", pit.acronym),
+            })?;
+
+        if !forked.change_pit_stop(driver, pit.lap, pit.compound) {
+            return Err(SimulationError::InvalidFork {
+                reason: format!(
+                    "{} can't pit on lap {} after forking on lap {}",
+                    pit.acronym, pit.lap, forked.lap
+                ),
+            });
+        }
+    }
+
+    for timeline in [&mut race, &mut forked] {
+        while !timeline.is_finished() {
+            timeline.simulate_lap();
+        }
+    }
+
+    Ok(ForkOutcome {
+        plan,
+        original: race.into_report(),
+        forked: forked.into_report(),
+    })
+}
@@ -0,0 +1,349 @@
+use crate::models::{Car, Driver, Team, TeamOrderPolicy};
+use crate::world::World;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// First and last name pools for each country a generated driver can come from.
+const NAME_POOLS: [(&str, &[&str], &[&str]); 10] = [
+    (
+        "United Kingdom",
+        &[
+            "Oliver", "Harry", "Jack", "George", "Callum", "Archie", "Freddie",
+        ],
+        &[
+            "Whitfield",
+            "Ashworth",
+            "Pemberton",
+            "Hale",
+            "Carrington",
+            "Blake",
+            "Fenwick",
+        ],
+    ),
+    (
+        "Italy",
+        &["Luca", "Matteo", "Lorenzo", "Davide", "Riccardo", "Tommaso"],
+        &[
+            "Marchetti",
+            "Rinaldi",
+            "Bellucci",
+            "Ferraro",
+            "Conti",
+            "Gallo",
+        ],
+    ),
+    (
+        "Germany",
+        &["Lukas", "Felix", "Jonas", "Leon", "Maximilian", "Paul"],
+        &["Keller", "Brandt", "Hoffmann", "Vogel", "Richter", "Krause"],
+    ),
+    (
+        "France",
+        &["Hugo", "Théo", "Louis", "Arthur", "Jules", "Raphaël"],
+        &[
+            "Moreau", "Lefèvre", "Girard", "Fontaine", "Rousseau", "Mercier",
+        ],
+    ),
+    (
+        "Spain",
+        &["Mateo", "Pablo", "Álvaro", "Diego", "Javier", "Sergio"],
+        &[
+            "Navarro", "Ortega", "Delgado", "Romero", "Castillo", "Vidal",
+        ],
+    ),
+    (
+        "Brazil",
+        &["Rafael", "Gabriel", "Thiago", "Bruno", "Enzo", "Caio"],
+        &[
+            "Duarte", "Barbosa", "Teixeira", "Moura", "Cardoso", "Azevedo",
+        ],
+    ),
+    (
+        "Netherlands",
+        &["Daan", "Sem", "Lars", "Thijs", "Bram", "Joost"],
+        &["de Groot", "Bakker", "Visser", "Smit", "Mulder", "van Dijk"],
+    ),
+    (
+        "Sweden",
+        &["Arvid", "Elias", "Oskar", "Nils", "Viktor", "Axel"],
+        &[
+            "Lindqvist",
+            "Berglund",
+            "Holm",
+            "Ekström",
+            "Nyberg",
+            "Sandberg",
+        ],
+    ),
+    (
+        "Japan",
+        &["Kai", "Haruto", "Ren", "Sota", "Yuto", "Daiki"],
+        &[
+            "Tanaka",
+            "Nakamura",
+            "Fujita",
+            "Kobayashi",
+            "Morita",
+            "Ishikawa",
+        ],
+    ),
+    (
+        "Australia",
+        &["Noah", "Lachlan", "Cooper", "Mitchell", "Riley", "Hamish"],
+        &[
+            "Fraser",
+            "McKenzie",
+            "Walsh",
+            "Donnelly",
+            "Hargreaves",
+            "Tully",
+        ],
+    ),
+];
+
+/// Names and numbers drawn for a driver before settling for one with a suffix. A long career
+/// keeps adding drivers, and sooner or later the name pools run out of unused acronyms.
+const MAX_DRAWS: usize = 100;
+
+/// Building blocks for generated team names.
+const TEAM_PREFIXES: [&str; 12] = [
+    "Apex", "Vortex", "Falcon", "Nova", "Titan", "Zenith", "Comet", "Phoenix", "Aurora", "Vector",
+    "Stellar", "Raptor",
+];
+const TEAM_SUFFIXES: [&str; 6] = [
+    "Racing",
+    "Motorsport",
+    "Grand Prix",
+    "Racing Team",
+    "F1 Team",
+    "Engineering",
+];
+
+/// Settings for a procedurally generated league. The same settings always give the same league.
+#[derive(Debug, Clone)]
+pub struct LeagueConfig {
+    pub seed: u64,
+    /// Number of teams on the grid, at most 12.
+    pub team_count: usize,
+    /// Gap in car rating points between the best and the worst team on the grid.
+    pub competitiveness_spread: u8,
+}
+
+impl LeagueConfig {
+    pub fn new(seed: u64) -> LeagueConfig {
+        LeagueConfig {
+            seed,
+            team_count: 10,
+            competitiveness_spread: 15,
+        }
+    }
+}
+
+/// Generates a fictional league: teams with their own colours and cars spread across
+/// `competitiveness_spread` rating points, two race drivers and a reserve each, plus a few
+/// rookies on the free-agent market. Names, acronyms and numbers are unique across the league.
+pub fn generate_league(config: &LeagueConfig) -> World {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let spread = config.competitiveness_spread.min(40) as f32;
+
+    let mut world = World::new();
+    let mut team_names: Vec<String> = Vec::new();
+
+    let team_count = config.team_count.clamp(1, TEAM_PREFIXES.len());
+    for index in 0..team_count {
+        // Every team gets its own prefix so names are easy to tell apart
+        let team_name = loop {
+            let prefix = TEAM_PREFIXES[rng.random_range(0..TEAM_PREFIXES.len())];
+            if !team_names.iter().any(|name| name.starts_with(prefix)) {
+                break format!(
+                    "{} {}",
+                    prefix,
+                    TEAM_SUFFIXES[rng.random_range(0..TEAM_SUFFIXES.len())]
+                );
+            }
+        };
+        team_names.push(team_name.clone());
+
+        // Teams are created strongest first, evenly spread down to the back of the grid
+        let strength = if team_count > 1 {
+            1.0 - index as f32 / (team_count - 1) as f32
+        } else {
+            1.0
+        };
+        let car = generate_car(&mut rng, &team_name, 80.0 + spread * (strength - 0.5));
+        let car = world.add_car(car);
+
+        let (nationality, _, _) = NAME_POOLS[rng.random_range(0..NAME_POOLS.len())];
+        let color = format!("{:06X}", rng.random_range(0..0x1000000u32));
+        // Top teams have more to spend on their drivers
+        let driver_budget = (10.0 + strength * 60.0 + rng.random_range(-5.0..5.0)).max(8.0);
+
+        let team = world.add_team(Team::new(
+            team_name,
+            color,
+            nationality.to_string(),
+            car,
+            driver_budget,
+            TeamOrderPolicy::FreeRacing,
+        ));
+
+        for _ in 0..2 {
+            let mut driver = generate_driver(&mut rng, world.all_drivers(), 80.0 + strength * 10.0);
+            let years = rng.random_range(1..=3);
+            let salary = driver.salary_demand(0.0).min(driver_budget / 2.0);
+            driver.sign_contract(years, salary);
+            let driver = world.add_driver(driver);
+            world.sign_race_driver(driver, team);
+        }
+
+        let number_one = world.team(team).drivers[0];
+        world.team_mut(team).order_policy = match rng.random_range(0..4) {
+            0 => TeamOrderPolicy::FreeRacing,
+            1 => TeamOrderPolicy::HoldPosition,
+            2 => TeamOrderPolicy::SwapForStrategy,
+            _ => TeamOrderPolicy::NumberOneDriver(number_one),
+        };
+
+        let reserve = generate_driver(&mut rng, world.all_drivers(), 78.0);
+        let reserve = world.add_driver(reserve);
+        world.sign_reserve(reserve, team);
+    }
+
+    for _ in 0..3 {
+        let mut rookie = generate_driver(&mut rng, world.all_drivers(), 80.0);
+        rookie.age = rng.random_range(18..22);
+        world.add_driver(rookie);
+    }
+
+    world
+}
+
+/// Generates a driver whose skill centres on `skill_centre`, with a name, acronym and number
+/// not used by anyone in `taken`. The driver is not signed to a team. If nothing unused turns
+/// up in `MAX_DRAWS` draws, the last name and acronym get a digit and the number is the first
+/// free one, so generation always ends.
+pub fn generate_driver(rng: &mut impl Rng, taken: &[Driver], skill_centre: f32) -> Driver {
+    let age: u8 = rng.random_range(18..36);
+    // Older drivers have seen more races
+    let experience_centre = 45.0 + (age - 18) as f32 * 2.8;
+
+    let mut draws = 0;
+    let (country, first_name, last_name, acronym, number) = loop {
+        let (country, first_names, last_names) = NAME_POOLS[rng.random_range(0..NAME_POOLS.len())];
+        let first_name = first_names[rng.random_range(0..first_names.len())];
+        let last_name = last_names[rng.random_range(0..last_names.len())];
+        let acronym: String = last_name
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .take(3)
+            .collect::<String>()
+            .to_uppercase();
+        let number: u8 = rng.random_range(2..100);
+        draws += 1;
+
+        let clashes = taken.iter().any(|driver| {
+            driver.full_name == format!("{} {}", first_name, last_name)
+                || driver.name_acronym == acronym
+                || driver.driver_number == number
+        });
+        if !clashes || draws == MAX_DRAWS {
+            break (country, first_name, last_name, acronym, number);
+        }
+    };
+
+    let last_name = unused(
+        std::iter::once(last_name.to_string())
+            .chain((2..).map(|suffix| format!("{} {}", last_name, suffix))),
+        |last_name| {
+            let full_name = format!("{} {}", first_name, last_name);
+            taken.iter().any(|driver| driver.full_name == full_name)
+        },
+    )
+    .expect("endless last names");
+    let acronym = unused(
+        std::iter::once(acronym.clone()).chain((1..).map(|suffix: u32| {
+            let digits = suffix.to_string();
+            let letters: String = acronym.chars().take(3 - digits.len().min(3)).collect();
+            letters + &digits
+        })),
+        |acronym| taken.iter().any(|driver| driver.name_acronym == *acronym),
+    )
+    .expect("endless acronyms");
+    // Past 99 numbers run out only with more than 250 drivers around
+    let number = unused(std::iter::once(number).chain(2..=u8::MAX), |number| {
+        taken.iter().any(|driver| driver.driver_number == *number)
+    })
+    .unwrap_or(number);
+
+    Driver::new(
+        first_name.to_string(),
+        last_name.clone(),
+        format!("{} {}", first_name, last_name),
+        acronym,
+        age,
+        country.to_string(),
+        number,
+        attribute(rng, skill_centre, 6.0),
+        attribute(rng, experience_centre, 8.0),
+        attribute(rng, 78.0, 8.0),
+        attribute(rng, skill_centre - 2.0, 6.0),
+    )
+}
+
+/// The first of `candidates` that isn't `used`.
+fn unused<T>(mut candidates: impl Iterator<Item = T>, used: impl Fn(&T) -> bool) -> Option<T> {
+    candidates.find(|candidate| !used(candidate))
+}
+
+/// Generates a car whose attributes centre on `rating`, with a little variation between areas.
+pub fn generate_car(rng: &mut impl Rng, team_name: &str, rating: f32) -> Car {
+    let initials: String = team_name
+        .split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .collect();
+
+    Car::new(
+        format!("{}-{}", initials, rng.random_range(1..30)),
+        attribute(rng, rating, 3.0),
+        attribute(rng, rating, 3.0),
+        attribute(rng, rating + 2.0, 3.0),
+        attribute(rng, rating, 3.0),
+    )
+}
+
+/// A rating drawn uniformly around `centre`, kept within the 40-99 range the models expect.
+fn attribute(rng: &mut impl Rng, centre: f32, spread: f32) -> u8 {
+    (centre + rng.random_range(-spread..=spread))
+        .round()
+        .clamp(40.0, 99.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_the_same_league() {
+        let league =
+            |seed| serde_json::to_value(generate_league(&LeagueConfig::new(seed))).unwrap();
+
+        assert_eq!(league(42), league(42));
+        assert_ne!(league(42), league(43));
+    }
+
+    #[test]
+    fn drivers_stay_unique_once_the_name_pools_run_out() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut taken: Vec<Driver> = Vec::new();
+        // More drivers than there are acronyms in the pools or numbers below 100
+        for _ in 0..150 {
+            let driver = generate_driver(&mut rng, &taken, 80.0);
+            assert!(taken.iter().all(|other| {
+                other.full_name != driver.full_name
+                    && other.name_acronym != driver.name_acronym
+                    && other.driver_number != driver.driver_number
+            }));
+            taken.push(driver);
+        }
+    }
+}
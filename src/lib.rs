@@ -0,0 +1,93 @@
+//! Formula 1 race weekends, seasons and careers, simulated lap by lap.
+//!
+//! The simulator runs on a [`World`] holding every driver, team and car behind stable IDs,
+//! loaded from a roster or generated. A [`Simulator`] built from a [`SimulationConfig`] runs
+//! the sessions of a weekend in order, or a whole season, and returns the results as data.
+//! Reporters decide how results are shown, if at all, and every event can be streamed as it
+//! happens.
+//!
+//! ```no_run
+//! use f1_team_and_race_simulator::reporter::JsonReporter;
+//! use f1_team_and_race_simulator::{SimulationConfig, Simulator};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // The simulator prints nothing on its own, everything goes to the reporter
+//! let config = SimulationConfig {
+//!     circuit: Some("monza".to_string()),
+//!     seed: Some(7),
+//!     ..SimulationConfig::default()
+//! };
+//! let mut simulator = Simulator::new(config)?;
+//! let mut reporter = JsonReporter::new(Box::new(std::io::stdout()));
+//! let report = simulator.race(&mut reporter)?;
+//!
+//! let winner = &report.results[0];
+//! println!("{} wins", simulator.world.driver_name(winner.driver));
+//! # Ok(())
+//! # }
+//! ```
+
+/// Calendars, setups and points.
+pub mod utils;
+
+/// Drivers, teams, cars, circuits, tyres and the results and events sessions produce.
+pub mod models;
+/// The registry owning everything on the grid, referred to by ID.
+pub mod world;
+/// Errors for loading rosters and running sessions.
+pub mod error;
+
+/// Configuration and the session runner, the usual way in.
+pub mod simulator;
+/// Practice, qualifying, sprints and races, run from a common input.
+pub mod session;
+/// A race weekend as a list of sessions.
+pub mod weekend;
+/// Lap-by-lap race state, for stepping through a race or changing it on the way.
+pub mod race;
+/// Championship standings over a run of races.
+pub mod season;
+/// The same race many times over, for the odds.
+pub mod montecarlo;
+/// What-if forks of a race from any lap.
+pub mod fork;
+/// Multi-season careers saved to a file.
+pub mod career;
+
+/// Roster files with a season's grid.
+pub mod roster;
+/// Procedurally generated leagues.
+pub mod generator;
+
+/// Where session results go: terminal tables, JSON, CSV or Markdown.
+pub mod reporter;
+/// The live stream of session events.
+pub mod events;
+/// Recorded races to play back.
+pub mod replay;
+/// Results as JSON and CSV files.
+pub mod export;
+/// A weekend as a self-contained HTML report.
+pub mod report;
+/// Race charts as SVG.
+pub mod charts;
+/// Timing tables, team colours and the plain output mode.
+pub mod render;
+
+/// Full-screen live timing in the terminal.
+pub mod live;
+/// Race-engineer mode, making the strategy calls for one team.
+pub mod engineer;
+/// Live timing for WebSocket clients.
+#[cfg(feature = "websocket")]
+pub mod feed;
+/// The local HTTP API.
+#[cfg(feature = "server")]
+pub mod server;
+
+mod market;
+mod pace;
+
+pub use error::{RosterError, SimulationError};
+pub use simulator::{RaceEntry, SimulationConfig, Simulator, WorldSource};
+pub use world::World;
@@ -0,0 +1,245 @@
+use crate::render::Style;
+use crate::replay::Replay;
+use crate::reporter::Reporter;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+/// Lap time used to pace the playback when there is no lap to go by, like before the start.
+const DEFAULT_LAP_TIME: f32 = 90.0;
+
+/// How long to wait for a key while the race isn't moving, before drawing again anyway.
+const IDLE_REDRAW: Duration = Duration::from_millis(500);
+
+/// How fast the live timing plays the race back.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Speed {
+    Paused,
+    RealTime, // a lap on screen takes as long as the leader's lap on track
+    Fast,     // ten times real time
+    Instant,  // straight to the chequered flag
+}
+
+impl Speed {
+    pub fn from_name(name: &str) -> Option<Speed> {
+        match name.to_lowercase().as_str() {
+            "pause" | "paused" => Some(Speed::Paused),
+            "1x" | "1" => Some(Speed::RealTime),
+            "10x" | "10" => Some(Speed::Fast),
+            "instant" => Some(Speed::Instant),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Speed::Paused => "Paused",
+            Speed::RealTime => "1x",
+            Speed::Fast => "10x",
+            Speed::Instant => "Instant",
+        }
+    }
+
+    /// Time on screen for a lap that took `lap_time` seconds on track, or `None` when the
+    /// race doesn't move on by itself.
+    fn lap_duration(&self, lap_time: f32) -> Option<Duration> {
+        match self {
+            Speed::Paused => None,
+            Speed::RealTime => Some(Duration::from_secs_f32(lap_time)),
+            Speed::Fast => Some(Duration::from_secs_f32(lap_time / 10.0)),
+            Speed::Instant => Some(Duration::ZERO),
+        }
+    }
+}
+
+/// Takes over the terminal for the live timing and puts it back the way it was when dropped,
+/// even if drawing fails part way.
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            io::stdout(),
+            terminal::EnterAlternateScreen,
+            terminal::DisableLineWrap,
+            cursor::Hide
+        )?;
+
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(
+            io::stdout(),
+            cursor::Show,
+            terminal::EnableLineWrap,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Every race control message up to the end of `lap`, oldest first.
+fn race_control(replay: &Replay, lap: u8) -> Vec<String> {
+    (1..=lap)
+        .flat_map(|lap| {
+            replay
+                .lap_summary(lap)
+                .into_iter()
+                .map(move |line| format!("L{:<3} {}", lap, line))
+        })
+        .collect()
+}
+
+/// How long the leader took over the lap after `lap`, to pace the playback by.
+fn next_lap_time(replay: &Replay, lap: u8) -> f32 {
+    replay
+        .timing_tower(lap + 1)
+        .first()
+        .map(|row| row.last_lap)
+        .filter(|lap_time| lap_time.is_finite() && *lap_time > 0.0)
+        .unwrap_or(DEFAULT_LAP_TIME)
+}
+
+/// Draws the lap counter, the timing tower and as much of the race control feed as fits,
+/// with the controls on the bottom line.
+fn draw(replay: &Replay, lap: u8, speed: Speed, style: Style) -> io::Result<()> {
+    let (_, height) = terminal::size()?;
+    let height = height as usize;
+
+    let counter = if lap == 0 {
+        "Starting Grid".to_string()
+    } else if lap >= replay.laps {
+        format!("Lap {}/{} - Chequered Flag", replay.laps, replay.laps)
+    } else {
+        format!("Lap {}/{}", lap, replay.laps)
+    };
+    let mut lines = vec![
+        style.bold(&format!("🏁 {} - {}", replay.circuit, counter)),
+        String::new(),
+    ];
+    lines.extend(replay.tower_table(lap).render(style).lines().map(String::from));
+    lines.push(String::new());
+    lines.push(style.bold("📻 Race Control"));
+
+    // The newest messages are the ones worth keeping when the screen is short
+    let feed = race_control(replay, lap);
+    let room = height.saturating_sub(lines.len() + 1);
+    lines.extend(feed[feed.len().saturating_sub(room)..].iter().cloned());
+
+    let controls = format!(
+        "{}  [Space] pause  [1] 1x  [2] 10x  [i] instant  [Left/Right] lap  [q] quit",
+        style.bold(speed.name())
+    );
+
+    let mut out = io::stdout();
+    for (row, line) in lines.into_iter().take(height.saturating_sub(1)).enumerate() {
+        queue!(
+            out,
+            cursor::MoveTo(0, row as u16),
+            Print(style.clean(line)),
+            terminal::Clear(ClearType::UntilNewLine)
+        )?;
+    }
+    queue!(
+        out,
+        terminal::Clear(ClearType::FromCursorDown),
+        cursor::MoveTo(0, height.saturating_sub(1) as u16),
+        Print(style.clean(controls)),
+        terminal::Clear(ClearType::UntilNewLine)
+    )?;
+    out.flush()
+}
+
+/// Full-screen live timing of a recorded race from `start_lap`, moving on a lap at a time at
+/// `speed` until the user quits. Space pauses, 1 and 2 pick 1x or 10x, i jumps to the flag and
+/// the arrow keys step a lap. The screen is drawn in `style`, and once the user quits the lap
+/// they stopped at goes to `reporter`.
+pub fn run_live(
+    replay: &Replay,
+    start_lap: u8,
+    speed: Speed,
+    style: Style,
+    reporter: &mut dyn Reporter,
+) -> Result<(), Box<dyn Error>> {
+    if !io::stdout().is_terminal() {
+        return Err("live timing needs a terminal, use `replay` to step through a race".into());
+    }
+
+    let mut lap = start_lap.min(replay.laps);
+    let mut speed = speed;
+    let mut resume_speed = if speed == Speed::Paused {
+        Speed::Fast
+    } else {
+        speed
+    };
+    let mut lap_started = Instant::now();
+
+    let screen = Screen::enter()?;
+    loop {
+        if speed == Speed::Instant {
+            lap = replay.laps;
+        }
+        draw(replay, lap, speed, style)?;
+
+        let due = speed
+            .lap_duration(next_lap_time(replay, lap))
+            .filter(|_| lap < replay.laps);
+        let timeout = due
+            .map(|duration| duration.saturating_sub(lap_started.elapsed()))
+            .unwrap_or(IDLE_REDRAW);
+
+        if !event::poll(timeout)? {
+            if due.is_some() {
+                lap += 1;
+                lap_started = Instant::now();
+            }
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            // Resizes and the like just need drawing again
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            // Raw mode turns Ctrl+C into a key press
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Char(' ') if speed == Speed::Paused => speed = resume_speed,
+            KeyCode::Char(' ') => {
+                resume_speed = speed;
+                speed = Speed::Paused;
+            }
+            KeyCode::Char('1') => speed = Speed::RealTime,
+            KeyCode::Char('2') => speed = Speed::Fast,
+            KeyCode::Char('i') => speed = Speed::Instant,
+            KeyCode::Right => lap = (lap + 1).min(replay.laps),
+            KeyCode::Left => {
+                lap = lap.saturating_sub(1);
+                // Going back from the flag would jump straight back to it
+                if speed == Speed::Instant {
+                    speed = Speed::Paused;
+                }
+            }
+            _ => continue,
+        }
+        lap_started = Instant::now();
+    }
+    drop(screen);
+
+    // Leave the tower where the user stopped watching in the scrollback
+    replay.show(lap, reporter);
+
+    Ok(())
+}
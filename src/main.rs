@@ -1,54 +1,738 @@
-mod utils;
-mod models;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+use f1_team_and_race_simulator::events::EventStream;
+#[cfg(feature = "websocket")]
+use f1_team_and_race_simulator::feed;
+use f1_team_and_race_simulator::models::{PlannedSubstitution, TeamId, Weather};
+use f1_team_and_race_simulator::render::Style;
+use f1_team_and_race_simulator::reporter::{ReportFormat, Reporter, TerminalReporter};
+use f1_team_and_race_simulator::season::Standings;
+#[cfg(feature = "server")]
+use f1_team_and_race_simulator::server;
+use f1_team_and_race_simulator::{
+    SimulationConfig, Simulator, World, career, charts, engineer, export, fork, live, replay,
+    report, roster,
+};
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::Path;
 
-use utils::*;
+/// Formula 1 race weekends, seasons and careers, simulated lap by lap.
+///
+/// Without a command a full race weekend is run, taking the same options as `weekend`.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    weekend: WeekendArgs,
+
+    /// Print without colour or emoji, for logs and CI
+    #[arg(long, global = true)]
+    plain: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the three free practice sessions
+    Practice(SessionArgs),
+    /// Qualify for the grid, from setups as practice would leave them
+    Qualify(SessionArgs),
+    /// Qualify and race, from setups as practice would leave them
+    Race(RaceArgs),
+    /// Qualify and race, then watch the race in full-screen live timing
+    Live(LiveArgs),
+    /// Qualify, then stream the race as live timing to WebSocket clients
+    #[cfg(feature = "websocket")]
+    Feed(FeedArgs),
+    /// Qualify, then run one team's pit wall in the race, calling stops lap by lap
+    Engineer(EngineerArgs),
+    /// Run a full race weekend
+    Weekend(WeekendArgs),
+    /// Race every round of the calendar, print the championship standings and run the silly
+    /// season they lead to
+    Season(SeasonArgs),
+    /// Race the same grid many times and count wins, podiums, points and retirements
+    Montecarlo(MonteCarloArgs),
+    /// Play a recorded race back from a lap (Enter steps, a number jumps)
+    Replay {
+        /// Replay file written with `--replay`
+        #[arg(default_value = "replay.json")]
+        file: String,
+        /// Lap to start from
+        #[arg(default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..))]
+        lap: u8,
+        /// Watch it in the full-screen live timing instead of stepping through it
+        #[arg(long)]
+        live: bool,
+        /// Live timing speed to start at: pause, 1x, 10x or instant
+        #[arg(long, default_value = "10x", value_parser = parse_speed, requires = "live")]
+        speed: live::Speed,
+    },
+    /// Serve a local HTTP API that runs sessions, weekends and seasons and returns JSON
+    #[cfg(feature = "server")]
+    Serve {
+        /// Address to listen on, local only unless told otherwise
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Start or continue a multi-season career
+    Career {
+        /// Save file, created when it doesn't exist
+        #[arg(default_value = "career.json")]
+        save: String,
+        /// Roster name or path for a new career
+        #[arg(long, default_value = roster::DEFAULT_ROSTER)]
+        roster: String,
+    },
+}
+
+/// Options every simulation takes.
+#[derive(Args)]
+struct CommonOptions {
+    /// Seed for the race, so a run can be repeated (random by default)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Roster from data/rosters by name, or a path to a roster file
+    #[arg(long, value_name = "NAME|PATH", default_value = roster::DEFAULT_ROSTER)]
+    roster: String,
+
+    /// Race a procedurally generated league from this seed instead of a roster
+    #[arg(long, value_name = "SEED", conflicts_with = "roster")]
+    generate: Option<u64>,
+
+    /// Race distance in laps, instead of the circuit's own
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..))]
+    laps: Option<u8>,
+
+    /// Track conditions: dry, damp or wet
+    #[arg(long, default_value = "dry", value_parser = parse_weather)]
+    weather: Weather,
+
+    /// Results as terminal tables, json, csv or markdown
+    #[arg(long, default_value = "terminal", value_parser = parse_format)]
+    format: ReportFormat,
+
+    /// Write the results to this file instead of the terminal
+    #[arg(long, value_name = "FILE")]
+    output: Option<String>,
+
+    /// Folder for every file written, created if needed
+    #[arg(long, value_name = "DIR")]
+    out_dir: Option<String>,
+}
+
+#[derive(Args)]
+struct SessionArgs {
+    #[command(flatten)]
+    common: CommonOptions,
+
+    /// Circuit by name or country (the season opener by default)
+    #[arg(long)]
+    circuit: Option<String>,
+
+    /// A reserve stepping in, like SAR:OSU:FP1:rookie or MAG:FIT:weekend:race-ban (repeatable)
+    #[arg(long, value_name = "OUT:IN[:SESSION[:REASON]]", value_parser = parse_substitution)]
+    substitute: Vec<PlannedSubstitution>,
+}
+
+#[derive(Args)]
+struct RaceArgs {
+    #[command(flatten)]
+    session: SessionArgs,
+
+    /// Stream every event as a JSON line to this file, or `-` for stdout instead of the text
+    #[arg(long, value_name = "FILE")]
+    events: Option<String>,
+
+    /// Record the race to a replay file
+    #[arg(long, value_name = "FILE", num_args = 0..=1, default_missing_value = "replay.json")]
+    replay: Option<String>,
+}
+
+#[derive(Args)]
+struct LiveArgs {
+    #[command(flatten)]
+    race: RaceArgs,
+
+    /// Speed to start at: pause, 1x, 10x or instant
+    #[arg(long, default_value = "10x", value_parser = parse_speed)]
+    speed: live::Speed,
+}
+
+#[cfg(feature = "websocket")]
+#[derive(Args)]
+struct FeedArgs {
+    #[command(flatten)]
+    race: RaceArgs,
+
+    /// Address to listen on, local only unless told otherwise
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    #[arg(long, default_value_t = 9001)]
+    port: u16,
+
+    /// How many times faster than real time the race runs, like 1x, 10x or 60x
+    #[arg(long, default_value = "10x", value_parser = parse_multiplier)]
+    speed: f32,
+
+    /// Seconds to wait after qualifying for clients to connect before the start
+    #[arg(long, value_name = "SECONDS", default_value_t = 5)]
+    start_in: u64,
+}
+
+#[derive(Args)]
+struct EngineerArgs {
+    #[command(flatten)]
+    race: RaceArgs,
+
+    /// Team whose pit wall you run, like ferrari or "red bull"
+    #[arg(long)]
+    team: String,
+}
+
+#[derive(Args)]
+struct WeekendArgs {
+    #[command(flatten)]
+    race: RaceArgs,
+
+    /// Write every session's results to JSON and CSV files in this folder
+    #[arg(long, value_name = "DIR", num_args = 0..=1, default_missing_value = "export")]
+    export: Option<String>,
+
+    /// Draw lap, gap-to-leader and tyre strategy charts as SVG files in this folder
+    #[arg(long, value_name = "DIR", num_args = 0..=1, default_missing_value = "charts")]
+    charts: Option<String>,
+
+    /// Write a self-contained HTML report of the weekend
+    #[arg(long, value_name = "FILE", num_args = 0..=1, default_missing_value = "report.html")]
+    report: Option<String>,
+
+    /// Replay the race to this lap and run a what-if from there
+    #[arg(long, value_name = "LAP")]
+    fork: Option<u8>,
+
+    /// A different pit stop for the fork, like VER:22:hard
+    #[arg(long, value_name = "CALL", requires = "fork", value_parser = parse_pit)]
+    pit: Option<fork::PitChange>,
+
+    /// Different luck for the fork
+    #[arg(long, value_name = "SEED", requires = "fork")]
+    fork_seed: Option<u64>,
+}
+
+#[derive(Args)]
+struct SeasonArgs {
+    #[command(flatten)]
+    common: CommonOptions,
+
+    /// Only race the first rounds of the calendar
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..))]
+    rounds: Option<u8>,
+}
+
+#[derive(Args)]
+struct MonteCarloArgs {
+    #[command(flatten)]
+    session: SessionArgs,
+
+    /// How many times to race
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+}
+
+fn parse_weather(name: &str) -> Result<Weather, String> {
+    Weather::from_name(name).ok_or_else(|| "use dry, damp or wet".to_string())
+}
+
+fn parse_format(name: &str) -> Result<ReportFormat, String> {
+    ReportFormat::from_name(name).ok_or_else(|| "use terminal, json, csv or markdown".to_string())
+}
+
+fn parse_speed(name: &str) -> Result<live::Speed, String> {
+    live::Speed::from_name(name).ok_or_else(|| "use pause, 1x, 10x or instant".to_string())
+}
+
+#[cfg(feature = "websocket")]
+fn parse_multiplier(text: &str) -> Result<f32, String> {
+    text.trim_end_matches(['x', 'X'])
+        .parse::<f32>()
+        .ok()
+        .filter(|speed| *speed > 0.0 && speed.is_finite())
+        .ok_or_else(|| "use a multiplier above zero, like 1x or 10x".to_string())
+}
+
+fn parse_substitution(spec: &str) -> Result<PlannedSubstitution, String> {
+    PlannedSubstitution::parse(spec).ok_or_else(|| {
+        "should look like SAR:OSU:FP1:rookie, the reason is injury, illness, race-ban or rookie"
+            .to_string()
+    })
+}
+
+fn parse_pit(spec: &str) -> Result<fork::PitChange, String> {
+    fork::PitChange::parse(spec).ok_or_else(|| "should look like VER:22:hard".to_string())
+}
+
+impl Cli {
+    /// Parses the command line. The weekend options only apply without a command, so giving
+    /// them before one (`--seed 7 race`) is an error rather than silently ignored. `--plain`
+    /// works on either side of the command.
+    fn parse_args<I, T>(args: I) -> Result<Cli, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
+        let mut command = Cli::command();
+        let matches = command.try_get_matches_from_mut(args)?;
+
+        if let Some((name, _)) = matches.subcommand() {
+            let misplaced = matches.ids().find(|id| {
+                id.as_str() != "plain"
+                    && matches.value_source(id.as_str()) == Some(ValueSource::CommandLine)
+            });
+            if let Some(id) = misplaced {
+                return Err(command.error(
+                    ErrorKind::ArgumentConflict,
+                    format!(
+                        "--{} belongs after the command: {} --{} ...",
+                        id.as_str().replace('_', "-"),
+                        name,
+                        id.as_str().replace('_', "-")
+                    ),
+                ));
+            }
+        }
+
+        Cli::from_arg_matches(&matches)
+    }
+}
 
 fn main() {
-    let drivers: Vec<models::Driver> = create_drivers();
-    let cars: Vec<(String, models::Car)> = create_cars();
-    let teams: Vec<models::Team> = create_teams(&drivers, &cars);
+    let cli = Cli::parse_args(std::env::args_os()).unwrap_or_else(|error| error.exit());
+    let style = Style::detect(cli.plain);
+
+    let result = match cli.command {
+        Some(Command::Practice(args)) => practice(&args, style),
+        Some(Command::Qualify(args)) => qualify(&args, style),
+        Some(Command::Race(args)) => race(&args, None, style),
+        Some(Command::Live(args)) => race(&args.race, Some(args.speed), style),
+        #[cfg(feature = "websocket")]
+        Some(Command::Feed(args)) => feed(&args, style),
+        Some(Command::Engineer(args)) => engineer(&args, style),
+        Some(Command::Weekend(args)) => weekend(&args, style),
+        None => weekend(&cli.weekend, style),
+        Some(Command::Season(args)) => season(&args, style),
+        Some(Command::Montecarlo(args)) => monte_carlo(&args, style),
+        Some(Command::Replay {
+            file,
+            lap,
+            live: true,
+            speed,
+        }) => replay::Replay::load(&file).and_then(|replay| {
+            live::run_live(
+                &replay,
+                lap,
+                speed,
+                style,
+                &mut TerminalReporter::stdout(style),
+            )
+        }),
+        Some(Command::Replay { file, lap, .. }) => replay::run_replay(
+            &file,
+            lap,
+            &mut TerminalReporter::stdout(style),
+            &mut prompt,
+        ),
+        #[cfg(feature = "server")]
+        Some(Command::Serve { host, port }) => {
+            server::run_server(&format!("{}:{}", host, port), &mut stderr(style))
+        }
+        Some(Command::Career { save, roster }) => career::run_career(
+            &save,
+            &roster,
+            &mut TerminalReporter::stdout(style),
+            &mut prompt,
+        ),
+    };
+
+    if let Err(error) = result {
+        stderr(style).note(&format!("❌ {}", error));
+        std::process::exit(1);
+    }
+}
+
+/// Terminal output that must stay out of stdout, like errors and progress.
+fn stderr(style: Style) -> TerminalReporter {
+    TerminalReporter::new(Box::new(io::stderr()), style)
+}
+
+/// Asks a question on the terminal and returns the trimmed answer.
+fn prompt(question: &str) -> String {
+    print!("{} ", question);
+    io::stdout().flush().ok();
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).ok();
+    answer.trim().to_string()
+}
+
+fn practice(args: &SessionArgs, style: Style) -> Result<(), Box<dyn Error>> {
+    let mut reporter = args.common.reporter(style)?;
+    let mut simulator = Simulator::new(args.config())?;
+    simulator.practice(reporter.as_mut())?;
+
+    reporter.finish()
+}
+
+fn qualify(args: &SessionArgs, style: Style) -> Result<(), Box<dyn Error>> {
+    let mut reporter = args.common.reporter(style)?;
+    let mut simulator = Simulator::new(args.config())?;
+    simulator.qualifying(reporter.as_mut())?;
+
+    reporter.finish()
+}
+
+/// Qualifying and the race, watched in the live timing afterwards when `live` is given.
+fn race(args: &RaceArgs, live: Option<live::Speed>, style: Style) -> Result<(), Box<dyn Error>> {
+    if live.is_some() && args.events.as_deref() == Some("-") {
+        return Err("live timing needs the terminal, stream the events to a file instead".into());
+    }
+    let mut events = args.events()?;
+    if live.is_some() {
+        events.start_recording();
+    }
+    let mut reporter = args.reporter(style)?;
+    let mut simulator = Simulator::new(args.session.config())?.with_events(events);
+    simulator.race(reporter.as_mut())?;
+    reporter.finish()?;
+
+    let replay = args.save_replay(
+        simulator.seed,
+        &mut simulator.events,
+        reporter.as_mut(),
+        style,
+    )?;
+    match live {
+        Some(speed) => live::run_live(
+            &replay,
+            0,
+            speed,
+            style,
+            &mut TerminalReporter::stdout(style),
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Qualifying, then the race streamed to every WebSocket client as it is run. Clients can
+/// connect from the start of qualifying until the flag.
+#[cfg(feature = "websocket")]
+fn feed(args: &FeedArgs, style: Style) -> Result<(), Box<dyn Error>> {
+    let events = args.race.events()?;
+    let mut reporter = args.race.reporter(style)?;
+    let mut simulator = Simulator::new(args.race.session.config())?.with_events(events);
+    let address = format!("{}:{}", args.host, args.port);
+    let live = feed::Feed::listen(&address)
+        .map_err(|error| format!("could not listen on {}: {}", address, error))?;
+    reporter.note(&format!("📡 Live timing on ws://{}", live.address()));
+
+    let entry = simulator.race_entry(reporter.as_mut())?;
+    reporter.note(&format!(
+        "\n📡 Lights out in {} s at {}x, {} clients connected",
+        args.start_in,
+        args.speed,
+        live.clients()
+    ));
+    std::thread::sleep(std::time::Duration::from_secs(args.start_in));
+    let report = feed::run_feed(
+        &simulator.world,
+        &simulator.circuit,
+        &entry,
+        simulator.seed,
+        args.speed,
+        &live,
+        &mut simulator.events,
+        &mut stderr(style),
+    )?;
+    live.close();
+    reporter.race(&simulator.world, &report);
+    reporter.finish()?;
+
+    args.race.save_replay(
+        simulator.seed,
+        &mut simulator.events,
+        reporter.as_mut(),
+        style,
+    )?;
+
+    Ok(())
+}
+
+/// Qualifying, then the race with the player making the calls for one team.
+fn engineer(args: &EngineerArgs, style: Style) -> Result<(), Box<dyn Error>> {
+    if args.race.events.as_deref() == Some("-") {
+        return Err("engineer mode needs the terminal, stream the events to a file instead".into());
+    }
+    let events = args.race.events()?;
+    let mut reporter = args.race.reporter(style)?;
+    let mut simulator = Simulator::new(args.race.session.config())?.with_events(events);
+    let team = args.team(&simulator.world)?;
+
+    let entry = simulator.race_entry(reporter.as_mut())?;
+    let report = engineer::run_engineer(
+        &simulator.world,
+        &simulator.circuit,
+        &entry,
+        simulator.seed,
+        team,
+        &mut simulator.events,
+        &mut TerminalReporter::stdout(style),
+        &mut prompt,
+    )?;
+    reporter.race(&simulator.world, &report);
+    reporter.finish()?;
+
+    args.race.save_replay(
+        simulator.seed,
+        &mut simulator.events,
+        reporter.as_mut(),
+        style,
+    )?;
+
+    Ok(())
+}
+
+fn weekend(args: &WeekendArgs, style: Style) -> Result<(), Box<dyn Error>> {
+    let common = &args.race.session.common;
+    let events = args.race.events()?;
+    let mut reporter = args.race.reporter(style)?;
+    let mut simulator = Simulator::new(args.race.session.config())?.with_events(events);
+    let fork_plan = args.fork.map(|lap| fork::ForkPlan {
+        lap,
+        seed: args.fork_seed,
+        pit: args.pit.clone(),
+    });
+
+    let weekend = simulator.weekend(fork_plan.as_ref(), reporter.as_mut())?;
+    reporter.finish()?;
+
+    args.race.save_replay(
+        weekend.seed,
+        &mut simulator.events,
+        reporter.as_mut(),
+        style,
+    )?;
+
+    if let Some(path) = &args.report {
+        let path = common.path(path)?;
+        report::write_weekend_report(
+            &path,
+            &weekend,
+            &Standings::from_race(&weekend.race.results),
+        )
+        .map_err(|error| format!("could not write report {}: {}", path, error))?;
+        reporter.note(&format!("📄 Weekend report written to {}", path));
+    }
+
+    if let Some(dir) = &args.charts {
+        let dir = common.path(dir)?;
+        let files = charts::write_charts(Path::new(&dir), &weekend)
+            .map_err(|error| format!("could not draw charts in {}: {}", dir, error))?;
+        reporter.note(&format!("📈 Drew {} charts in {}", files.len(), dir));
+    }
+
+    if let Some(dir) = &args.export {
+        let dir = common.path(dir)?;
+        let files = export::export_weekend(Path::new(&dir), &weekend)
+            .map_err(|error| format!("could not export to {}: {}", dir, error))?;
+        reporter.note(&format!("\n💾 Exported {} files to {}", files.len(), dir));
+    }
+
+    Ok(())
+}
+
+fn season(args: &SeasonArgs, style: Style) -> Result<(), Box<dyn Error>> {
+    let mut reporter = args.common.reporter(style)?;
+    let mut simulator = Simulator::new(args.common.config())?;
+    let standings = simulator.season(args.rounds, reporter.as_mut())?;
+    simulator.silly_season(&standings, reporter.as_mut());
+
+    reporter.finish()
+}
+
+fn monte_carlo(args: &MonteCarloArgs, style: Style) -> Result<(), Box<dyn Error>> {
+    let mut reporter = args.session.common.reporter(style)?;
+    let mut simulator = Simulator::new(args.session.config())?;
+    simulator.monte_carlo(args.runs, reporter.as_mut())?;
+
+    reporter.finish()
+}
+
+impl CommonOptions {
+    /// The simulation asked for on the command line, at the season opener.
+    fn config(&self) -> SimulationConfig {
+        SimulationConfig {
+            roster: self.roster.clone(),
+            generate: self.generate,
+            circuit: None,
+            laps: self.laps,
+            weather: self.weather,
+            seed: self.seed,
+            substitutions: Vec::new(),
+        }
+    }
+
+    /// A reporter for the chosen format, writing to `--output` or stdout. A file gets no
+    /// colour.
+    fn reporter(&self, style: Style) -> Result<Box<dyn Reporter>, Box<dyn Error>> {
+        let (writer, style): (Box<dyn Write>, _) = match &self.output {
+            Some(path) => {
+                let path = self.path(path)?;
+                let file = std::fs::File::create(&path)
+                    .map_err(|error| format!("could not open output file {}: {}", path, error))?;
+                (Box::new(io::BufWriter::new(file)), style.uncoloured())
+            }
+            None => (Box::new(io::stdout()), style),
+        };
+
+        Ok(self.format.reporter(writer, style))
+    }
+
+    /// `path` inside `--out-dir` when one is given, creating the folder.
+    fn path(&self, path: &str) -> Result<String, Box<dyn Error>> {
+        match &self.out_dir {
+            Some(dir) => {
+                std::fs::create_dir_all(dir)
+                    .map_err(|error| format!("could not create folder {}: {}", dir, error))?;
+                Ok(Path::new(dir).join(path).to_string_lossy().into_owned())
+            }
+            None => Ok(path.to_string()),
+        }
+    }
+}
+
+impl SessionArgs {
+    /// The simulation asked for, at the circuit picked with `--circuit`.
+    fn config(&self) -> SimulationConfig {
+        SimulationConfig {
+            circuit: self.circuit.clone(),
+            substitutions: self.substitute.clone(),
+            ..self.common.config()
+        }
+    }
+}
+
+impl EngineerArgs {
+    /// The team asked for with `--team`, matching any part of its name.
+    fn team(&self, world: &World) -> Result<TeamId, Box<dyn Error>> {
+        let wanted = self.team.to_lowercase();
+        let teams: Vec<_> = world
+            .teams()
+            .iter()
+            .filter(|team| team.name.to_lowercase().contains(&wanted))
+            .collect();
 
-    println!();
-    println!("{:#?}", teams);
-    println!();
+        match teams.as_slice() {
+            [team] => Ok(team.id),
+            [] => Err(format!("no team matches `{}`", self.team).into()),
+            _ => Err(format!(
+                "`{}` matches {}, be more specific",
+                self.team,
+                teams
+                    .iter()
+                    .map(|team| team.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .into()),
+        }
+    }
+}
 
-    // Simulate Free Practice sessions
-    println!("\n🏁 Free Practice 1 Summary 🏁");
-    let fp1_results = simulate_practice_session(&drivers, &cars, "FP1");
+impl RaceArgs {
+    /// The event stream asked for with `--events`, recording too when a replay is wanted.
+    fn events(&self) -> Result<EventStream, Box<dyn Error>> {
+        let mut events = match self.events.as_deref() {
+            None => EventStream::disabled(),
+            Some("-") => EventStream::stdout(),
+            Some(path) => {
+                let path = self.session.common.path(path)?;
+                EventStream::to_file(&path)
+                    .map_err(|error| format!("could not open event file {}: {}", path, error))?
+            }
+        };
+        if self.replay.is_some() {
+            events.start_recording();
+        }
 
-    // println!();
-    // println!("{:#?}", fp1_results);
-    // println!();
+        Ok(events)
+    }
 
-    println!("\n🏁 Free Practice 2 Summary 🏁");
-    let fp2_results = simulate_practice_session(&drivers, &cars, "FP2");
+    /// The reporter for the chosen format. With the events on stdout, the terminal tables go
+    /// to stderr so the two don't get mixed up.
+    fn reporter(&self, style: Style) -> Result<Box<dyn Reporter>, Box<dyn Error>> {
+        let common = &self.session.common;
+        if self.events.as_deref() == Some("-") && common.format == ReportFormat::Terminal {
+            return Ok(Box::new(stderr(style)));
+        }
 
-    // println!();
-    // println!("{:#?}", fp2_results);
-    // println!();
+        common.reporter(style)
+    }
 
-    println!("\n🏁 Free Practice 3 Summary 🏁");
-    let fp3_results = simulate_practice_session(&drivers, &cars, "FP3");
+    /// The replay recorded from `events`, saved when `--replay` asks for it. If the event stream
+    /// was closed early, a warning goes to stderr.
+    fn save_replay(
+        &self,
+        seed: u64,
+        events: &mut EventStream,
+        reporter: &mut dyn Reporter,
+        style: Style,
+    ) -> Result<replay::Replay, Box<dyn Error>> {
+        if let Some(error) = events.take_error() {
+            stderr(style).note(&format!("⚠️ Event stream closed: {}", error));
+        }
 
-    // println!();
-    // println!("{:#?}", fp3_results);
-    // println!();
+        let replay = replay::Replay::new(seed, events.take_recording());
+        if let Some(path) = &self.replay {
+            let path = self.session.common.path(path)?;
+            replay
+                .save(&path)
+                .map_err(|error| format!("could not save replay {}: {}", path, error))?;
+            reporter.note(&format!("📼 Replay saved to {}", path));
+        }
 
-    // Simulate Qualification
-    println!("\n🏁 Qualification Summary 🏁");
-    let qualification_results = simulate_qualification(&drivers, &cars);
+        Ok(replay)
+    }
+}
 
-    // println!();
-    // println!("{:#?}", qualification_results);
-    // println!();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Qualification summary in order
-    qualification_summary(&qualification_results);
+    #[test]
+    fn plain_goes_before_or_after_the_command() {
+        for args in [["f1", "--plain", "qualify"], ["f1", "qualify", "--plain"]] {
+            let cli = Cli::parse_args(args).unwrap();
+            assert!(cli.plain);
+            assert!(matches!(cli.command, Some(Command::Qualify(_))));
+        }
+    }
 
-    // Simulate Race Day
-    println!("\n🏁 Race Results Summary 🏁");
-    let race_results = simulate_race(&drivers, &cars);
+    #[test]
+    fn weekend_options_before_a_command_are_rejected() {
+        let error = Cli::parse_args(["f1", "--seed", "7", "race"])
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
 
-    // Give Race Weekend Summary
-    race_weekend_summary(&race_results);
-}
\ No newline at end of file
+        let cli = Cli::parse_args(["f1", "--seed", "7"]).unwrap();
+        assert!(cli.command.is_none());
+    }
+}
@@ -0,0 +1,409 @@
+use crate::models::{DriverId, TeamId};
+use crate::world::World;
+use rand::Rng;
+use serde::Serialize;
+
+/// Team name given to drivers without a race seat.
+pub const FREE_AGENT: &str = "Free Agent";
+
+/// A single move made during the silly season.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "move", rename_all = "snake_case")]
+pub enum TransferMove {
+    Retired {
+        driver: DriverId,
+        team: Option<TeamId>,
+        age: u8,
+    },
+    Extended {
+        driver: DriverId,
+        team: TeamId,
+        years: u8,
+        salary: f32,
+    },
+    Released {
+        driver: DriverId,
+        team: TeamId,
+    },
+    Signed {
+        driver: DriverId,
+        from: TeamId,
+        to: TeamId,
+        years: u8,
+        salary: f32,
+    },
+    Promoted {
+        driver: DriverId,
+        team: TeamId,
+        years: u8,
+        salary: f32,
+    },
+    SignedFreeAgent {
+        driver: DriverId,
+        team: TeamId,
+        years: u8,
+        salary: f32,
+    },
+}
+
+impl TransferMove {
+    pub fn info(&self, world: &World) -> String {
+        let emoji = match self {
+            TransferMove::Retired { .. } => "👋",
+            TransferMove::Extended { .. } => "✍️",
+            TransferMove::Released { .. } => "🚪",
+            TransferMove::Signed { .. } => "🔁",
+            TransferMove::Promoted { .. } => "🆕",
+            TransferMove::SignedFreeAgent { .. } => "🤝",
+        };
+
+        format!("{} {}", emoji, self.description(world))
+    }
+
+    /// The move in words, like "Carlos Sainz moves from Scuderia Ferrari to Williams ...".
+    pub fn description(&self, world: &World) -> String {
+        match *self {
+            TransferMove::Retired { driver, team, age } => format!(
+                "{} retires from {} at the age of {}",
+                world.driver_name(driver),
+                team.map(|team| world.team_name(team)).unwrap_or(FREE_AGENT),
+                age
+            ),
+            TransferMove::Extended {
+                driver,
+                team,
+                years,
+                salary,
+            } => format!(
+                "{} extends with {} for {} year(s) at ${:.1}M",
+                world.driver_name(driver),
+                world.team_name(team),
+                years,
+                salary
+            ),
+            TransferMove::Released { driver, team } => format!(
+                "{} is released by {}",
+                world.driver_name(driver),
+                world.team_name(team)
+            ),
+            TransferMove::Signed {
+                driver,
+                from,
+                to,
+                years,
+                salary,
+            } => format!(
+                "{} moves from {} to {} for {} year(s) at ${:.1}M",
+                world.driver_name(driver),
+                world.team_name(from),
+                world.team_name(to),
+                years,
+                salary
+            ),
+            TransferMove::Promoted {
+                driver,
+                team,
+                years,
+                salary,
+            } => format!(
+                "Reserve {} is promoted to a race seat at {} for {} year(s) at ${:.1}M",
+                world.driver_name(driver),
+                world.team_name(team),
+                years,
+                salary
+            ),
+            TransferMove::SignedFreeAgent {
+                driver,
+                team,
+                years,
+                salary,
+            } => format!(
+                "{} joins {} as a free agent for {} year(s) at ${:.1}M",
+                world.driver_name(driver),
+                world.team_name(team),
+                years,
+                salary
+            ),
+        }
+    }
+}
+
+/// Runs the transfer market between two seasons.
+///
+/// Every race driver gets a year older and a year closer to the end of their contract. Drivers
+/// out of contract may retire, be re-signed or be released. Teams then fill their open seats,
+/// strongest car first, promoting their own reserves before turning to the market for the most
+/// valuable driver they can afford from the released drivers and the free agents. Retirements are capped by the number of free agents so every seat can
+/// always be filled. Returns every move in the order it happened.
+///
+/// `season_points` holds the points each driver scored last season. Every decision that is
+/// left to chance draws on `rng`.
+pub fn run_silly_season(
+    world: &mut World,
+    season_points: &[(DriverId, u32)],
+    rng: &mut impl Rng,
+) -> Vec<TransferMove> {
+    let mut moves = Vec::new();
+
+    let best_points = season_points
+        .iter()
+        .map(|(_, points)| *points)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let performance = |driver: DriverId| -> f32 {
+        let points = season_points
+            .iter()
+            .find(|(id, _)| *id == driver)
+            .map(|(_, points)| *points)
+            .unwrap_or(0);
+
+        points as f32 / best_points as f32
+    };
+
+    // Age everyone and run down their contracts
+    let mut seated = world.race_drivers();
+    for &id in &seated {
+        let driver = world.driver_mut(id);
+        driver.age += 1;
+        if let Some(contract) = driver.contract.as_mut() {
+            contract.years_remaining = contract.years_remaining.saturating_sub(1);
+        }
+    }
+
+    // Veterans out of contract may call it a day, oldest first
+    seated.sort_by_key(|&id| std::cmp::Reverse(world.driver(id).age));
+    let newcomers = world.free_agents().len();
+    let mut retirements = 0;
+    for id in seated {
+        let driver = world.driver(id);
+        let out_of_contract = driver.contract.as_ref().is_none_or(|c| c.is_expired());
+        let retirement_chance = (driver.age.saturating_sub(34) as f64 * 0.12).min(0.9);
+
+        if out_of_contract
+            && retirements < newcomers
+            && retirement_chance > 0.0
+            && rng.random_bool(retirement_chance)
+        {
+            moves.push(TransferMove::Retired {
+                driver: id,
+                team: driver.team,
+                age: driver.age,
+            });
+            world.retire(id);
+            retirements += 1;
+        }
+    }
+
+    // Teams decide whether to keep drivers whose contracts have run out
+    let mut released: Vec<(DriverId, TeamId)> = Vec::new();
+    for team_id in world.team_ids() {
+        let team = world.team(team_id);
+        let team_drivers = team.drivers.clone();
+        let driver_budget = team.driver_budget;
+
+        let mut committed: f32 = team_drivers
+            .iter()
+            .filter_map(|&id| world.driver(id).contract.as_ref())
+            .filter(|c| !c.is_expired())
+            .map(|c| c.salary)
+            .sum();
+        // Better cars set a higher bar for who they keep
+        let threshold = world.car(team.car).average_rating() - 12.0 + rng.random_range(-3.0..3.0);
+
+        for id in team_drivers {
+            let driver = world.driver(id);
+            if driver.contract.as_ref().is_some_and(|c| !c.is_expired()) {
+                continue;
+            }
+
+            let salary = driver.salary_demand(performance(id));
+            if driver.market_value(performance(id)) >= threshold
+                && committed + salary <= driver_budget
+            {
+                let years = rng.random_range(1..=3);
+                world.driver_mut(id).sign_contract(years, salary);
+                committed += salary;
+                moves.push(TransferMove::Extended {
+                    driver: id,
+                    team: team_id,
+                    years,
+                    salary,
+                });
+            } else {
+                moves.push(TransferMove::Released {
+                    driver: id,
+                    team: team_id,
+                });
+                world.release(id);
+                released.push((id, team_id));
+            }
+        }
+    }
+
+    // Fill the open seats, strongest teams get first pick
+    let mut pick_order = world.team_ids();
+    pick_order.sort_by(|&a, &b| {
+        let rating = |team: TeamId| world.car(world.team(team).car).average_rating();
+        rating(b).total_cmp(&rating(a))
+    });
+
+    for team_id in pick_order {
+        while world.team(team_id).drivers.len() < 2 {
+            let committed: f32 = world
+                .team(team_id)
+                .drivers
+                .iter()
+                .filter_map(|&id| world.driver(id).contract.as_ref())
+                .map(|c| c.salary)
+                .sum();
+            let budget_left = world.team(team_id).driver_budget - committed;
+            let released_by = |id: DriverId| {
+                released
+                    .iter()
+                    .find(|(driver, _)| *driver == id)
+                    .map(|(_, team)| *team)
+            };
+
+            // A reserve waiting in the wings gets the seat first, the most valuable one if
+            // there are several
+            let reserve = world
+                .team(team_id)
+                .reserve_drivers
+                .iter()
+                .copied()
+                .max_by(|&a, &b| {
+                    world
+                        .driver(a)
+                        .market_value(performance(a))
+                        .total_cmp(&world.driver(b).market_value(performance(b)))
+                });
+
+            // Otherwise the most valuable affordable driver the team didn't just let go, or
+            // failing that the cheapest one available
+            let pool = world.free_agents();
+            let affordable = pool
+                .iter()
+                .copied()
+                .filter(|&id| released_by(id) != Some(team_id))
+                .filter(|&id| world.driver(id).salary_demand(performance(id)) <= budget_left)
+                .max_by(|&a, &b| {
+                    world
+                        .driver(a)
+                        .market_value(performance(a))
+                        .total_cmp(&world.driver(b).market_value(performance(b)))
+                });
+            let cheapest = pool.iter().copied().min_by(|&a, &b| {
+                world
+                    .driver(a)
+                    .salary_demand(performance(a))
+                    .total_cmp(&world.driver(b).salary_demand(performance(b)))
+            });
+
+            let Some(id) = reserve.or(affordable).or(cheapest) else {
+                break;
+            };
+
+            let salary = world
+                .driver(id)
+                .salary_demand(performance(id))
+                .min(budget_left.max(1.0));
+            let years = rng.random_range(1..=3);
+            world.driver_mut(id).sign_contract(years, salary);
+            world.sign_race_driver(id, team_id);
+
+            moves.push(match released_by(id) {
+                _ if reserve == Some(id) => TransferMove::Promoted {
+                    driver: id,
+                    team: team_id,
+                    years,
+                    salary,
+                },
+                Some(from) if from == team_id => TransferMove::Extended {
+                    driver: id,
+                    team: team_id,
+                    years,
+                    salary,
+                },
+                Some(from) => TransferMove::Signed {
+                    driver: id,
+                    from,
+                    to: team_id,
+                    years,
+                    salary,
+                },
+                None => TransferMove::SignedFreeAgent {
+                    driver: id,
+                    team: team_id,
+                    years,
+                    salary,
+                },
+            });
+        }
+    }
+
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roster::{DEFAULT_ROSTER, Roster};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn silly_season_leaves_a_valid_grid() {
+        let mut world = Roster::load(DEFAULT_ROSTER).unwrap().to_world().unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        // A few winters in a row, so contracts run out and veterans retire
+        for _ in 0..5 {
+            let season_points: Vec<(DriverId, u32)> = world
+                .race_drivers()
+                .into_iter()
+                .enumerate()
+                .map(|(index, driver)| (driver, 400 / (index as u32 + 1)))
+                .collect();
+            run_silly_season(&mut world, &season_points, &mut rng);
+
+            let seated = world.race_drivers();
+            for team in world.teams() {
+                assert_eq!(team.drivers.len(), 2, "{} has a free seat", team.name);
+                for &driver in team.drivers.iter().chain(&team.reserve_drivers) {
+                    assert_eq!(world.driver(driver).team, Some(team.id));
+                }
+            }
+            for (index, driver) in seated.iter().enumerate() {
+                assert!(
+                    !seated[..index].contains(driver),
+                    "{:?} has two seats",
+                    driver
+                );
+                assert!(!world.free_agents().contains(driver));
+            }
+        }
+    }
+
+    #[test]
+    fn a_reserve_is_promoted_to_an_open_seat() {
+        let mut world = Roster::load(DEFAULT_ROSTER).unwrap().to_world().unwrap();
+        let team = world.team_ids()[0];
+        for id in world.team(team).reserve_drivers.clone() {
+            world.release(id);
+        }
+        let reserve = world.free_agents()[0];
+        world.sign_reserve(reserve, team);
+        let leaving = world.team(team).drivers[0];
+        world.release(leaving);
+
+        let moves = run_silly_season(&mut world, &[], &mut StdRng::seed_from_u64(1));
+
+        assert!(world.team(team).drivers.contains(&reserve));
+        assert!(!world.team(team).reserve_drivers.contains(&reserve));
+        assert!(moves.iter().any(|transfer| matches!(
+            transfer,
+            TransferMove::Promoted { driver, team: to, .. } if *driver == reserve && *to == team
+        )));
+    }
+}
@@ -1,7 +1,11 @@
+use crate::models::CarId;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Car {
+    #[serde(default)]
+    pub id: CarId, // assigned when the car is added to a `World`
     pub name: String,
     pub engine_power: u8,
     pub tyre_management: u8,
@@ -18,6 +22,7 @@ impl Car {
         aerodynamics: u8,
     ) -> Car {
         Car {
+            id: CarId::default(),
             name,
             engine_power,
             tyre_management,
@@ -50,4 +55,13 @@ impl Car {
             + self.tyre_management as f32 * random_number
             + self.reliability as f32 * random_number
     }
-}
\ No newline at end of file
+
+    /// Average of all car attributes, without any random variation.
+    pub fn average_rating(&self) -> f32 {
+        (self.engine_power as f32
+            + self.tyre_management as f32
+            + self.reliability as f32
+            + self.aerodynamics as f32)
+            / 4.0
+    }
+}
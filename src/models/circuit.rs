@@ -0,0 +1,48 @@
+use crate::models::{CarSetup, Weather};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Circuit {
+    pub name: String,
+    pub country: String,
+    pub laps: u8,
+    pub base_lap_time: f32,    // seconds for a perfect lap on fresh softs with no fuel
+    pub tyre_degradation: f32, // 1.0 is an average circuit, higher eats tyres faster
+    pub ideal_setup: CarSetup, // unknown to the teams until they find it in practice
+    #[serde(default)]
+    pub weather: Weather,
+}
+
+impl Circuit {
+    pub fn new(
+        name: String,
+        country: String,
+        laps: u8,
+        base_lap_time: f32,
+        tyre_degradation: f32,
+        ideal_setup: CarSetup,
+    ) -> Circuit {
+        Circuit {
+            name,
+            country,
+            laps,
+            base_lap_time,
+            tyre_degradation,
+            ideal_setup,
+            weather: Weather::Dry,
+        }
+    }
+
+    pub fn info(&self) -> String {
+        match self.weather {
+            Weather::Dry => format!("🏟️ {} ({}) - {} laps", self.name, self.country, self.laps),
+            weather => format!(
+                "🏟️ {} ({}) - {} laps - 🌧️ {}",
+                self.name,
+                self.country,
+                self.laps,
+                weather.name()
+            ),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contract {
+    pub years_remaining: u8,
+    pub salary: f32, // in millions of USD per season
+}
+
+impl Contract {
+    pub fn new(years_remaining: u8, salary: f32) -> Contract {
+        Contract {
+            years_remaining,
+            salary,
+        }
+    }
+
+    pub fn info(&self) -> String {
+        format!(
+            "📝 Contract: {} year(s) remaining at ${:.1}M per season",
+            self.years_remaining, self.salary
+        )
+    }
+
+    /// A contract has expired once no seasons remain on it.
+    pub fn is_expired(&self) -> bool {
+        self.years_remaining == 0
+    }
+}
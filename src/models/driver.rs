@@ -1,7 +1,11 @@
-use crate::models::Car;
+use crate::models::{Car, Contract, DriverId, TeamId};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Driver {
+    #[serde(default)]
+    pub id: DriverId, // assigned when the driver is added to a `World`
     pub first_name: String,
     pub last_name: String,
     pub full_name: String,
@@ -13,10 +17,14 @@ pub struct Driver {
     pub experience_level: u8,
     pub aggression_level: u8,
     pub consistency_level: u8,
-    pub team_name: String,
+    #[serde(default)]
+    pub team: Option<TeamId>, // race or reserve seat, `None` for free agents
+    #[serde(default)]
+    pub contract: Option<Contract>,
 }
 
 impl Driver {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         first_name: String,
         last_name: String,
@@ -29,9 +37,9 @@ impl Driver {
         experience_level: u8,
         aggression_level: u8,
         consistency_level: u8,
-        team_name: String,
     ) -> Driver {
         Driver {
+            id: DriverId::default(),
             first_name,
             last_name,
             full_name,
@@ -43,7 +51,8 @@ impl Driver {
             experience_level,
             aggression_level,
             consistency_level,
-            team_name,
+            team: None,
+            contract: None,
         }
     }
 
@@ -58,7 +67,6 @@ impl Driver {
             📈 Experience: {}
             🔥 Aggression: {}
             🎯 Consistency: {}
-            🏎Team: {}
             ",
             self.full_name,
             self.last_name,
@@ -70,7 +78,6 @@ impl Driver {
             self.experience_level,
             self.aggression_level,
             self.consistency_level,
-            self.team_name,
         )
     }
 
@@ -100,4 +107,34 @@ impl Driver {
 
         base_chance + aggression_factor + reliability_factor
     }
-}
\ No newline at end of file
+
+    /// Gives the driver a new contract, replacing any previous one. Moving the driver between
+    /// teams is done through `World::sign_race_driver`.
+    pub fn sign_contract(&mut self, years: u8, salary: f32) {
+        self.contract = Some(Contract::new(years, salary));
+    }
+
+    /// Estimates how attractive the driver is on the market (0-100 scale) based on rating,
+    /// last season's performance (0.0 to 1.0 relative to the champion) and age.
+    pub fn market_value(&self, performance: f32) -> f32 {
+        let rating = self.overall_rating() * 25.0; // overall_rating is out of 4.0
+        let age_penalty = self.age.saturating_sub(34) as f32 * 1.5;
+
+        rating * 0.8 + performance * 100.0 * 0.2 - age_penalty
+    }
+
+    /// Salary in millions of USD the driver asks for, based on their market value.
+    pub fn salary_demand(&self, performance: f32) -> f32 {
+        let value = (self.market_value(performance) - 55.0).max(0.0) / 40.0;
+
+        1.0 + value * value * 60.0
+    }
+
+    /// Whether the driver decides to ignore a team order this time. The more aggressive the
+    /// driver, the more likely they are to race on regardless.
+    pub fn ignores_team_order(&self, rng: &mut impl Rng) -> bool {
+        let chance = self.aggression_level.saturating_sub(50) as f64 / 100.0 * 0.6;
+
+        rng.random_bool(chance.min(1.0))
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// How hard a car's power unit is run. Pushing is quicker but breaks down more often, saving
+/// the engine is slower but kinder to it.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum EngineMode {
+    Conserve,
+    #[default]
+    Standard,
+    Push,
+}
+
+impl EngineMode {
+    /// Parses a mode from its name, ignoring case.
+    pub fn from_name(name: &str) -> Option<EngineMode> {
+        match name.to_lowercase().as_str() {
+            "conserve" | "save" => Some(EngineMode::Conserve),
+            "standard" | "normal" => Some(EngineMode::Standard),
+            "push" => Some(EngineMode::Push),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EngineMode::Conserve => "Conserve",
+            EngineMode::Standard => "Standard",
+            EngineMode::Push => "Push",
+        }
+    }
+
+    /// Seconds per lap slower (or quicker, when negative) than the standard mode.
+    pub fn lap_time_offset(&self) -> f32 {
+        match self {
+            EngineMode::Conserve => 0.35,
+            EngineMode::Standard => 0.0,
+            EngineMode::Push => -0.3,
+        }
+    }
+
+    /// How many times more likely a failure is than in the standard mode.
+    pub fn failure_factor(&self) -> f32 {
+        match self {
+            EngineMode::Conserve => 0.6,
+            EngineMode::Standard => 1.0,
+            EngineMode::Push => 1.8,
+        }
+    }
+}
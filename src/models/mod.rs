@@ -1,9 +1,37 @@
 mod driver;
+mod ids;
 mod car;
+mod circuit;
+mod contract;
+mod engine_mode;
 mod team;
+mod team_order;
+mod tyre;
+mod pit_wall;
+mod practice;
+mod race_event;
+mod race_report;
 mod race_result;
+mod setup;
+mod substitution;
+mod weather;
 
 pub use car::Car;
+pub use circuit::Circuit;
+pub use contract::Contract;
 pub use driver::Driver;
+pub use engine_mode::EngineMode;
+pub use ids::{CarId, DriverId, TeamId};
+pub use pit_wall::PitWallCall;
+pub use practice::{LongRunPace, PracticeRun, PracticeSession, RunType};
+pub use race_event::{Entrant, Flag, RaceEvent};
+pub use race_report::{LapRecord, RaceReport};
 pub use race_result::RaceResult;
+pub use setup::CarSetup;
+pub use substitution::{
+    PlannedSubstitution, Substitution, SubstitutionReason, SubstitutionScope,
+};
 pub use team::Team;
+pub use team_order::{TeamOrder, TeamOrderKind, TeamOrderPolicy};
+pub use tyre::TyreCompound;
+pub use weather::Weather;
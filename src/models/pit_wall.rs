@@ -0,0 +1,18 @@
+use crate::models::{EngineMode, TyreCompound};
+use serde::{Deserialize, Serialize};
+
+/// A strategy call made from the pit wall in race-engineer mode. Team orders are recorded as
+/// team orders, like the ones the other teams give.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "call", rename_all = "snake_case")]
+pub enum PitWallCall {
+    /// Pit at the end of the next lap, in place of the next planned stop.
+    Box {
+        compound: TyreCompound,
+    },
+    /// Skip the next planned stop.
+    StayOut,
+    EngineMode {
+        mode: EngineMode,
+    },
+}
@@ -0,0 +1,96 @@
+use crate::models::{DriverId, RaceResult, TeamId, TyreCompound};
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+
+/// A type of run a team can do during practice.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RunType {
+    QualifyingSim,
+    LongRun(TyreCompound),
+    AeroTest,
+}
+
+impl RunType {
+    pub fn name(&self) -> String {
+        match self {
+            RunType::QualifyingSim => "Qualifying Sim".to_string(),
+            RunType::LongRun(compound) => format!("Long Run ({})", compound.name()),
+            RunType::AeroTest => "Aero Test".to_string(),
+        }
+    }
+
+    pub fn compound(&self) -> TyreCompound {
+        match self {
+            RunType::QualifyingSim => TyreCompound::Soft,
+            RunType::LongRun(compound) => *compound,
+            RunType::AeroTest => TyreCompound::Medium,
+        }
+    }
+
+    pub fn laps(&self) -> usize {
+        match self {
+            RunType::QualifyingSim => 2,
+            RunType::LongRun(_) => 10,
+            RunType::AeroTest => 3,
+        }
+    }
+
+    /// Fuel in kilograms the car leaves the garage with.
+    pub fn starting_fuel_kg(&self) -> f32 {
+        match self {
+            RunType::QualifyingSim => 10.0,
+            RunType::LongRun(_) => 90.0,
+            RunType::AeroTest => 40.0,
+        }
+    }
+}
+
+/// One run out of the garage and the lap times set on it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PracticeRun {
+    pub driver: DriverId,
+    pub team: TeamId,
+    pub run_type: RunType,
+    pub lap_times: Vec<f32>,
+}
+
+impl PracticeRun {
+    pub fn best_lap(&self) -> f32 {
+        self.lap_times.iter().cloned().fold(f32::MAX, f32::min)
+    }
+
+    pub fn info(&self, world: &World) -> String {
+        let laps: Vec<String> = self.lap_times.iter().map(|t| format!("{:.3}", t)).collect();
+
+        format!(
+            "{} - {} | {}: {}",
+            world.driver_name(self.driver),
+            world.team_name(self.team),
+            self.run_type.name(),
+            laps.join(", ")
+        )
+    }
+}
+
+/// Average pace of a long run, with the effect of the fuel burned off taken out
+/// so runs with different fuel loads can be compared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LongRunPace {
+    pub driver: DriverId,
+    pub team: TeamId,
+    pub compound: TyreCompound,
+    pub laps: usize,
+    pub average_lap_time: f32,
+    pub fuel_corrected_lap_time: f32,
+}
+
+/// Everything that came out of a practice session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PracticeSession {
+    pub name: String,
+    pub results: Vec<RaceResult>,
+    pub runs: Vec<PracticeRun>,
+    pub long_runs: Vec<LongRunPace>,
+    #[serde(default)]
+    pub setup_quality: Vec<(DriverId, f32)>, // how close each car ended up to the ideal setup
+}
@@ -0,0 +1,96 @@
+use crate::models::{DriverId, PitWallCall, RaceResult, TeamId, TeamOrder, TyreCompound};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Flag {
+    Green,
+    Yellow,
+    SafetyCar,
+    Chequered,
+}
+
+/// A driver taking part in a session, so consumers of the event stream can put names to IDs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entrant {
+    pub driver: DriverId,
+    pub name: String,
+    pub acronym: String,
+    pub team: TeamId,
+    pub team_name: String,
+    #[serde(default)]
+    pub team_color: String, // missing from replays recorded before team colours were shown
+}
+
+/// Something that happened during a session, in the order it happened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RaceEvent {
+    SessionStart {
+        session: String,
+        circuit: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        laps: Option<u8>, // race distance, practice and qualifying run to the clock
+        entrants: Vec<Entrant>,
+    },
+    /// A lap set by a driver. Practice and qualifying laps have no running position.
+    LapCompleted {
+        session: String,
+        lap: u8,
+        driver: DriverId,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        position: Option<u8>,
+        lap_time: f32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        total_time: Option<f32>,
+        compound: TyreCompound,
+    },
+    Overtake {
+        lap: u8,
+        driver: DriverId,
+        passed: DriverId,
+        position: u8,
+    },
+    PitStop {
+        lap: u8,
+        driver: DriverId,
+        compound: TyreCompound, // the compound fitted
+        stop: u8,
+    },
+    Retirement {
+        lap: u8,
+        driver: DriverId,
+    },
+    Flag {
+        lap: u8,
+        flag: Flag,
+    },
+    TeamOrder {
+        order: TeamOrder,
+    },
+    /// A call made by the player on the pit wall in race-engineer mode, at the end of `lap`.
+    PitWallCall {
+        lap: u8,
+        driver: DriverId,
+        call: PitWallCall,
+    },
+    SessionEnd {
+        session: String,
+        classification: Vec<RaceResult>,
+    },
+}
+
+impl RaceEvent {
+    /// The lap the event belongs to. Session starts and ends aren't tied to a lap.
+    pub fn lap(&self) -> Option<u8> {
+        match self {
+            RaceEvent::LapCompleted { lap, .. }
+            | RaceEvent::Overtake { lap, .. }
+            | RaceEvent::PitStop { lap, .. }
+            | RaceEvent::Retirement { lap, .. }
+            | RaceEvent::Flag { lap, .. }
+            | RaceEvent::PitWallCall { lap, .. } => Some(*lap),
+            RaceEvent::TeamOrder { order } => Some(order.lap),
+            RaceEvent::SessionStart { .. } | RaceEvent::SessionEnd { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::models::{DriverId, RaceResult, TeamOrder, TyreCompound};
+use serde::{Deserialize, Serialize};
+
+/// One driver's lap of the race, kept for charts and strategy summaries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LapRecord {
+    pub lap: u8,
+    pub driver: DriverId,
+    pub position: u8, // at the end of the lap
+    pub lap_time: f32,
+    pub total_time: f32,
+    pub compound: TyreCompound, // the tyres the lap was driven on
+    pub pitted: bool,           // came in at the end of the lap
+}
+
+/// A run on one set of tyres.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stint {
+    pub compound: TyreCompound,
+    pub first_lap: u8,
+    pub last_lap: u8,
+}
+
+/// Classification of a race along with what happened during it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaceReport {
+    pub results: Vec<RaceResult>,
+    pub team_orders: Vec<TeamOrder>,
+    #[serde(default)]
+    pub laps: Vec<LapRecord>, // every lap of every driver, in lap order
+}
+
+impl RaceReport {
+    /// A driver's laps in order.
+    pub fn laps_of(&self, driver: DriverId) -> Vec<&LapRecord> {
+        self.laps
+            .iter()
+            .filter(|lap| lap.driver == driver)
+            .collect()
+    }
+
+    /// A driver's tyre stints, split at every pit stop.
+    pub fn stints(&self, driver: DriverId) -> Vec<Stint> {
+        let mut stints: Vec<Stint> = Vec::new();
+        let mut new_stint = true;
+
+        for lap in self.laps_of(driver) {
+            match stints.last_mut() {
+                Some(stint) if !new_stint => stint.last_lap = lap.lap,
+                _ => stints.push(Stint {
+                    compound: lap.compound,
+                    first_lap: lap.lap,
+                    last_lap: lap.lap,
+                }),
+            }
+            new_stint = lap.pitted;
+        }
+
+        stints
+    }
+
+    /// Seconds behind the leader at the end of every lap the driver completed.
+    pub fn gaps_to_leader(&self, driver: DriverId) -> Vec<(u8, f32)> {
+        self.laps_of(driver)
+            .into_iter()
+            .filter_map(|lap| {
+                let leader = self
+                    .laps
+                    .iter()
+                    .find(|other| other.lap == lap.lap && other.position == 1)?;
+
+                Some((lap.lap, lap.total_time - leader.total_time))
+            })
+            .collect()
+    }
+}
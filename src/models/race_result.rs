@@ -1,40 +1,44 @@
-#[derive(Debug, Clone)]
+use crate::models::{DriverId, TeamId};
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RaceResult {
-    pub driver_name: String,
-    pub team_name: String,
+    pub driver: DriverId,
+    pub team: TeamId,
     pub finish_position: u8,
-    pub finish_time_seconds: u8,
+    pub finish_time_seconds: f32,
     pub dnf: bool,
 }
 
 impl RaceResult {
     pub fn new(
-        driver_name: String,
-        team_name: String,
+        driver: DriverId,
+        team: TeamId,
         finish_position: u8,
-        finish_time_seconds: u8,
+        finish_time_seconds: f32,
         dnf: bool,
     ) -> RaceResult {
         RaceResult {
-            driver_name,
-            team_name,
+            driver,
+            team,
             finish_position,
             finish_time_seconds,
             dnf,
         }
     }
 
-    pub fn info(&self) -> String {
+    pub fn info(&self, world: &World) -> String {
         format!(
             "🏁 Race Result
             👤 Driver: {}
             🏢 Team: {}
             🏆 Finish Position: {}
-            ⏱️ Finish Time: {}s
+            ⏱️ Finish Time: {:.3}s
             ❌ DNF: {}
             ",
-            self.driver_name,
-            self.team_name,
+            world.driver_name(self.driver),
+            world.team_name(self.team),
             self.finish_position,
             self.finish_time_seconds,
             if self.dnf { "Yes" } else { "No" }
@@ -0,0 +1,61 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Car setup parameters, each on a 0-100 scale. A balance below 50 leans towards
+/// oversteer, above 50 towards understeer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CarSetup {
+    pub downforce: u8,
+    pub ride_height: u8,
+    pub balance: u8,
+}
+
+impl CarSetup {
+    pub fn new(downforce: u8, ride_height: u8, balance: u8) -> CarSetup {
+        CarSetup {
+            downforce,
+            ride_height,
+            balance,
+        }
+    }
+
+    /// The middle-of-the-road setup every car arrives at a circuit with.
+    pub fn baseline() -> CarSetup {
+        CarSetup::new(50, 50, 50)
+    }
+
+    pub fn info(&self) -> String {
+        format!(
+            "🪛 Downforce: {} | Ride Height: {} | Balance: {}",
+            self.downforce, self.ride_height, self.balance
+        )
+    }
+
+    /// How close the setup is to the optimum, from 0.0 (way off) to 1.0 (spot on).
+    pub fn quality(&self, optimum: &CarSetup) -> f32 {
+        let error = (self.downforce.abs_diff(optimum.downforce) as f32
+            + self.ride_height.abs_diff(optimum.ride_height) as f32
+            + self.balance.abs_diff(optimum.balance) as f32)
+            / 3.0;
+
+        (1.0 - error / 20.0).clamp(0.0, 1.0)
+    }
+
+    /// Moves the setup towards the optimum using the driver's feedback after a run.
+    /// Experienced drivers give more accurate feedback and engineers act on it more boldly.
+    pub fn refine(&mut self, optimum: &CarSetup, experience_level: u8, rng: &mut impl Rng) {
+        let noise = (100 - experience_level.min(100)) as f32 / 3.0;
+        let step = 0.15 + experience_level as f32 / 400.0;
+
+        let mut adjust = |current: u8, target: u8| -> u8 {
+            let feedback = target as f32 + rng.random_range(-noise..=noise);
+            let adjusted = current as f32 + (feedback - current as f32) * step;
+
+            adjusted.round().clamp(0.0, 100.0) as u8
+        };
+
+        self.downforce = adjust(self.downforce, optimum.downforce);
+        self.ride_height = adjust(self.ride_height, optimum.ride_height);
+        self.balance = adjust(self.balance, optimum.balance);
+    }
+}
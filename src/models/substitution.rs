@@ -0,0 +1,174 @@
+use crate::error::SimulationError;
+use crate::models::{DriverId, TeamId};
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SubstitutionReason {
+    Injury,
+    Illness,
+    RaceBan,
+    RookieOuting,
+}
+
+impl SubstitutionReason {
+    pub fn from_name(name: &str) -> Option<SubstitutionReason> {
+        match name.to_lowercase().replace(['-', '_'], " ").as_str() {
+            "injury" => Some(SubstitutionReason::Injury),
+            "illness" => Some(SubstitutionReason::Illness),
+            "race ban" | "ban" => Some(SubstitutionReason::RaceBan),
+            "rookie outing" | "rookie" => Some(SubstitutionReason::RookieOuting),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SubstitutionReason::Injury => "injury",
+            SubstitutionReason::Illness => "illness",
+            SubstitutionReason::RaceBan => "race ban",
+            SubstitutionReason::RookieOuting => "rookie outing",
+        }
+    }
+}
+
+/// Which part of the weekend a substitution covers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SubstitutionScope {
+    Session(String),
+    Weekend,
+}
+
+/// A reserve driver standing in for one of the team's race drivers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Substitution {
+    pub team: TeamId,
+    pub driver_out: DriverId,
+    pub driver_in: DriverId,
+    pub reason: SubstitutionReason,
+    pub scope: SubstitutionScope,
+}
+
+impl Substitution {
+    pub fn new(
+        team: TeamId,
+        driver_out: DriverId,
+        driver_in: DriverId,
+        reason: SubstitutionReason,
+        scope: SubstitutionScope,
+    ) -> Substitution {
+        Substitution {
+            team,
+            driver_out,
+            driver_in,
+            reason,
+            scope,
+        }
+    }
+
+    pub fn applies_to(&self, session_name: &str) -> bool {
+        match &self.scope {
+            SubstitutionScope::Session(name) => name.eq_ignore_ascii_case(session_name),
+            SubstitutionScope::Weekend => true,
+        }
+    }
+
+    pub fn info(&self, world: &World) -> String {
+        let scope = match &self.scope {
+            SubstitutionScope::Session(name) => name.clone(),
+            SubstitutionScope::Weekend => "the whole weekend".to_string(),
+        };
+
+        format!(
+            "🔄 {}: {} replaces {} for {} ({})",
+            world.team_name(self.team),
+            world.driver_name(self.driver_in),
+            world.driver_name(self.driver_out),
+            scope,
+            self.reason.name()
+        )
+    }
+}
+
+/// A substitution asked for by driver, written `SAR:OSU:FP1:rookie` on the command line:
+/// the driver stepping out, the reserve stepping in, the session or `weekend` (the default)
+/// and the reason (an injury by default). Drivers are given by acronym or full name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedSubstitution {
+    pub driver_out: String,
+    pub driver_in: String,
+    pub reason: SubstitutionReason,
+    pub scope: SubstitutionScope,
+}
+
+impl PlannedSubstitution {
+    pub fn parse(spec: &str) -> Option<PlannedSubstitution> {
+        let mut parts = spec.split(':');
+        let driver_out = parts.next().filter(|name| !name.is_empty())?.to_string();
+        let driver_in = parts.next().filter(|name| !name.is_empty())?.to_string();
+        let scope = match parts.next() {
+            None => SubstitutionScope::Weekend,
+            Some(scope) if scope.eq_ignore_ascii_case("weekend") => SubstitutionScope::Weekend,
+            Some(session) => SubstitutionScope::Session(session.to_string()),
+        };
+        let reason = match parts.next() {
+            Some(reason) => SubstitutionReason::from_name(reason)?,
+            None => SubstitutionReason::Injury,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(PlannedSubstitution {
+            driver_out,
+            driver_in,
+            reason,
+            scope,
+        })
+    }
+
+    /// The substitution in `world`. The driver stepping out must have a race seat and the one
+    /// stepping in must be a reserve of the same team.
+    pub fn resolve(&self, world: &World) -> Result<Substitution, SimulationError> {
+        let find = |name: &str| {
+            world
+                .all_drivers()
+                .iter()
+                .find(|driver| {
+                    driver.name_acronym.eq_ignore_ascii_case(name)
+                        || driver.full_name.eq_ignore_ascii_case(name)
+                })
+                .map(|driver| driver.id)
+                .ok_or_else(|| SimulationError::InvalidSubstitution {
+                    reason: format!("no driver {}", name),
+                })
+        };
+        let driver_out = find(&self.driver_out)?;
+        let driver_in = find(&self.driver_in)?;
+
+        let team = world
+            .teams()
+            .iter()
+            .find(|team| team.drivers.contains(&driver_out))
+            .ok_or_else(|| SimulationError::InvalidSubstitution {
+                reason: format!("{} has no race seat", world.driver_name(driver_out)),
+            })?;
+        if !team.reserve_drivers.contains(&driver_in) {
+            return Err(SimulationError::InvalidSubstitution {
+                reason: format!(
+                    "{} is not a reserve driver at {}",
+                    world.driver_name(driver_in),
+                    team.name
+                ),
+            });
+        }
+
+        Ok(Substitution::new(
+            team.id,
+            driver_out,
+            driver_in,
+            self.reason,
+            self.scope.clone(),
+        ))
+    }
+}
@@ -1,60 +1,79 @@
-use crate::models::{Car, Driver};
+use crate::models::{CarId, DriverId, TeamId, TeamOrderPolicy};
+use crate::world::World;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Team {
+    pub id: TeamId, // assigned when the team is added to a `World`
     pub name: String,
     pub team_color: String,
     pub nationality: String,
-    pub drivers: Vec<Driver>,
-    pub car: Car,
+    pub drivers: Vec<DriverId>,
+    pub reserve_drivers: Vec<DriverId>,
+    pub car: CarId,
+    pub driver_budget: f32, // in millions of USD per season
+    pub order_policy: TeamOrderPolicy,
 }
 
 impl Team {
+    /// Creates a team without drivers, they are signed through the `World`.
     pub fn new(
         name: String,
         team_color: String,
         nationality: String,
-        drivers: Vec<Driver>,
-        car: Car,
+        car: CarId,
+        driver_budget: f32,
+        order_policy: TeamOrderPolicy,
     ) -> Team {
         Team {
+            id: TeamId::default(),
             name,
             team_color,
             nationality,
-            drivers,
+            drivers: Vec::new(),
+            reserve_drivers: Vec::new(),
             car,
+            driver_budget,
+            order_policy,
         }
     }
 
-    pub fn info(&self) -> String {
+    pub fn info(&self, world: &World) -> String {
         let mut drivers_info = String::new();
         for driver in &self.drivers {
             drivers_info.push_str("    - ");
-            drivers_info.push_str(&driver.full_name);
+            drivers_info.push_str(world.driver_name(*driver));
             drivers_info.push('\n');
         }
+        for driver in &self.reserve_drivers {
+            drivers_info.push_str("    - ");
+            drivers_info.push_str(world.driver_name(*driver));
+            drivers_info.push_str(" (reserve)\n");
+        }
 
         format!(
-            "🏎️ Team: {}\n🎨 Color: {}\n🌐 Nationality: {}\n👥 Drivers:\n{}{}",
+            "🏎️ Team: {}\n🎨 Color: {}\n🌐 Nationality: {}\n💰 Driver Budget: ${:.1}M\n📻 Team Orders: {}\n👥 Drivers:\n{}{}",
             self.name,
             self.team_color,
             self.nationality,
+            self.driver_budget,
+            self.order_policy.name(world),
             drivers_info,
-            self.car.info()
+            world.car(self.car).info()
         )
     }
 
-    pub fn average_driver_rating(&self) -> f32 {
+    pub fn average_driver_rating(&self, world: &World) -> f32 {
         let mut total = 0.0;
 
         for driver in &self.drivers {
-            total += driver.overall_rating();
+            total += world.driver(*driver).overall_rating();
         }
 
         total / self.drivers.len() as f32
     }
 
-    pub fn team_performance_score(&self) -> f32 {
-        (self.car.overall_performance() + self.average_driver_rating()) / 2.0
+    pub fn team_performance_score(&self, world: &World) -> f32 {
+        (world.car(self.car).overall_performance() + self.average_driver_rating(world)) / 2.0
     }
-}
\ No newline at end of file
+}
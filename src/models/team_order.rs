@@ -0,0 +1,65 @@
+use crate::models::{DriverId, TeamId};
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+
+/// How a team wants its two drivers to race each other.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TeamOrderPolicy {
+    /// Let them race, no orders are ever given.
+    FreeRacing,
+    /// Whoever is ahead stays ahead once the two are fighting.
+    HoldPosition,
+    /// The driver behind is let through when they are clearly faster, e.g. on fresher tyres.
+    SwapForStrategy,
+    /// This driver is always prioritised over their teammate.
+    NumberOneDriver(DriverId),
+}
+
+impl TeamOrderPolicy {
+    pub fn name(&self, world: &World) -> String {
+        match self {
+            TeamOrderPolicy::FreeRacing => "Free racing".to_string(),
+            TeamOrderPolicy::HoldPosition => "Hold position".to_string(),
+            TeamOrderPolicy::SwapForStrategy => "Swap for strategy".to_string(),
+            TeamOrderPolicy::NumberOneDriver(driver) => {
+                format!("Number one driver: {}", world.driver_name(*driver))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TeamOrderKind {
+    HoldPosition,
+    LetTeammateThrough,
+}
+
+/// An order given over the radio during a race, and whether the driver listened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamOrder {
+    pub lap: u8,
+    pub team: TeamId,
+    pub kind: TeamOrderKind,
+    pub driver: DriverId, // the driver receiving the order
+    pub teammate: DriverId,
+    pub obeyed: bool,
+}
+
+impl TeamOrder {
+    pub fn info(&self, world: &World) -> String {
+        let teammate = world.driver_name(self.teammate);
+        let instruction = match self.kind {
+            TeamOrderKind::HoldPosition => format!("hold position behind {}", teammate),
+            TeamOrderKind::LetTeammateThrough => format!("let {} through", teammate),
+        };
+
+        format!(
+            "📻 Lap {}: {} tells {} to {}{}",
+            self.lap,
+            world.team_name(self.team),
+            world.driver_name(self.driver),
+            instruction,
+            if self.obeyed { "" } else { " - ORDER IGNORED" }
+        )
+    }
+}
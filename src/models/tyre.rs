@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TyreCompound {
+    Soft,
+    Medium,
+    Hard,
+}
+
+impl TyreCompound {
+    /// Parses a compound from its name or short name, ignoring case.
+    pub fn from_name(name: &str) -> Option<TyreCompound> {
+        match name.to_lowercase().as_str() {
+            "soft" | "s" => Some(TyreCompound::Soft),
+            "medium" | "m" => Some(TyreCompound::Medium),
+            "hard" | "h" => Some(TyreCompound::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TyreCompound::Soft => "Soft",
+            TyreCompound::Medium => "Medium",
+            TyreCompound::Hard => "Hard",
+        }
+    }
+
+    /// Hex colour of the sidewall marking, without the `#`.
+    pub fn colour(&self) -> &'static str {
+        match self {
+            TyreCompound::Soft => "DA291C",
+            TyreCompound::Medium => "FFD12E",
+            TyreCompound::Hard => "9A9A9A",
+        }
+    }
+
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            TyreCompound::Soft => "S",
+            TyreCompound::Medium => "M",
+            TyreCompound::Hard => "H",
+        }
+    }
+
+    /// Seconds per lap slower than the softest compound on fresh tyres.
+    pub fn pace_offset(&self) -> f32 {
+        match self {
+            TyreCompound::Soft => 0.0,
+            TyreCompound::Medium => 0.5,
+            TyreCompound::Hard => 1.0,
+        }
+    }
+
+    /// Seconds lost per lap of tyre age at an average circuit.
+    pub fn degradation_per_lap(&self) -> f32 {
+        match self {
+            TyreCompound::Soft => 0.12,
+            TyreCompound::Medium => 0.07,
+            TyreCompound::Hard => 0.04,
+        }
+    }
+}
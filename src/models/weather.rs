@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// Track conditions for a weekend. The wetter it is, the slower the laps and the more the
+/// experienced and consistent drivers stand out.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Weather {
+    #[default]
+    Dry,
+    Damp,
+    Wet,
+}
+
+impl Weather {
+    /// Parses the weather from its name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Weather> {
+        match name.to_lowercase().as_str() {
+            "dry" => Some(Weather::Dry),
+            "damp" => Some(Weather::Damp),
+            "wet" => Some(Weather::Wet),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Weather::Dry => "Dry",
+            Weather::Damp => "Damp",
+            Weather::Wet => "Wet",
+        }
+    }
+
+    /// Seconds per lap slower than in the dry for a driver with no experience.
+    pub fn lap_time_penalty(&self) -> f32 {
+        match self {
+            Weather::Dry => 0.0,
+            Weather::Damp => 4.0,
+            Weather::Wet => 9.0,
+        }
+    }
+
+    /// How much more lap times vary than in the dry.
+    pub fn spread_factor(&self) -> f32 {
+        match self {
+            Weather::Dry => 1.0,
+            Weather::Damp => 1.5,
+            Weather::Wet => 2.0,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::error::SimulationError;
+use crate::events::EventStream;
+use crate::models::{CarSetup, Circuit, DriverId, RaceResult, TeamId};
+use crate::session::{Race, Session, SessionInput, session_rng};
+use crate::utils::*;
+use crate::world::World;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// How one driver fared over every run of a Monte Carlo simulation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloRow {
+    pub driver_id: DriverId,
+    pub driver: String,
+    pub acronym: String,
+    pub team_id: TeamId,
+    pub team: String,
+    pub wins: u32,
+    pub podiums: u32,
+    pub points: u32,
+    pub dnfs: u32,
+    pub average_finish: f32, // retirements count at the position they were classified in
+}
+
+impl MonteCarloRow {
+    pub fn win_share(&self, runs: u32) -> f32 {
+        self.wins as f32 / runs.max(1) as f32 * 100.0
+    }
+
+    pub fn podium_share(&self, runs: u32) -> f32 {
+        self.podiums as f32 / runs.max(1) as f32 * 100.0
+    }
+
+    pub fn dnf_share(&self, runs: u32) -> f32 {
+        self.dnfs as f32 / runs.max(1) as f32 * 100.0
+    }
+
+    pub fn average_points(&self, runs: u32) -> f32 {
+        self.points as f32 / runs.max(1) as f32
+    }
+}
+
+/// Races the same grid `runs` times, each with its own seed drawn from `seed`, to see how
+/// often every driver wins, reaches the podium or retires. Sorted by points scored.
+pub fn run_monte_carlo(
+    world: &World,
+    drivers: &[DriverId],
+    circuit: &Circuit,
+    setups: &[(DriverId, CarSetup)],
+    grid: &[RaceResult],
+    runs: u32,
+    seed: u64,
+) -> Result<Vec<MonteCarloRow>, SimulationError> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut rows = Vec::new();
+    for driver in drivers {
+        let team = world.team_of(*driver)?;
+        rows.push(MonteCarloRow {
+            driver_id: *driver,
+            driver: world.driver_name(*driver).to_string(),
+            acronym: world.driver(*driver).name_acronym.clone(),
+            team_id: team,
+            team: world.team_name(team).to_string(),
+            wins: 0,
+            podiums: 0,
+            points: 0,
+            dnfs: 0,
+            average_finish: 0.0,
+        });
+    }
+
+    let mut setups = setups.to_vec();
+    for _ in 0..runs {
+        let race = Race::after("Qualifying").run(SessionInput {
+            world,
+            entrants: drivers,
+            circuit,
+            setups: &mut setups,
+            grid,
+            rng: session_rng(rng.random(), "Race"),
+            events: &mut EventStream::disabled(),
+        })?;
+
+        for (result, (_, points)) in race
+            .classification
+            .iter()
+            .zip(championship_points(&race.classification))
+        {
+            let Some(row) = rows.iter_mut().find(|row| row.driver_id == result.driver) else {
+                continue;
+            };
+            row.points += points;
+            row.average_finish += result.finish_position as f32;
+            if result.dnf {
+                row.dnfs += 1;
+            } else {
+                row.wins += (result.finish_position == 1) as u32;
+                row.podiums += (result.finish_position <= 3) as u32;
+            }
+        }
+    }
+
+    for row in &mut rows {
+        row.average_finish /= runs.max(1) as f32;
+    }
+    rows.sort_by(|a, b| {
+        b.points
+            .cmp(&a.points)
+            .then(a.average_finish.total_cmp(&b.average_finish))
+    });
+
+    Ok(rows)
+}
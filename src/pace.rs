@@ -0,0 +1,76 @@
+use crate::models::{Car, CarSetup, Circuit, Driver, TyreCompound};
+use rand::Rng;
+
+/// Seconds lost per lap for every kilogram of fuel on board.
+pub const FUEL_EFFECT_PER_KG: f32 = 0.03;
+
+/// Fuel burned per lap in kilograms.
+pub const FUEL_PER_LAP_KG: f32 = 1.6;
+
+/// Conditions a single lap is driven in.
+#[derive(Debug, Clone, Copy)]
+pub struct LapConditions {
+    pub compound: TyreCompound,
+    pub tyre_age: u8,
+    pub fuel_kg: f32,
+}
+
+/// Lap time in seconds for a driver and car, without any lap-to-lap variation.
+///
+/// Starts from the circuit's reference lap and adds the driver's and car's deficit to a perfect
+/// package, the cost of a setup away from the optimum, the compound and its wear, fuel load and
+/// the weather.
+pub fn ideal_lap_time(
+    driver: &Driver,
+    car: &Car,
+    circuit: &Circuit,
+    setup: &CarSetup,
+    conditions: &LapConditions,
+) -> f32 {
+    let driver_pace = driver.skill_level as f32 * 0.6
+        + driver.experience_level as f32 * 0.2
+        + driver.consistency_level as f32 * 0.2;
+    let car_pace = car.engine_power as f32 * 0.4
+        + car.aerodynamics as f32 * 0.4
+        + car.tyre_management as f32 * 0.2;
+
+    let driver_deficit = (100.0 - driver_pace) * 0.06;
+    let car_deficit = (100.0 - car_pace) * 0.1;
+    let setup_deficit = (1.0 - setup.quality(&circuit.ideal_setup)) * 1.0;
+
+    let wear_rate = conditions.compound.degradation_per_lap()
+        * circuit.tyre_degradation
+        * (1.5 - car.tyre_management as f32 / 100.0);
+    let tyre_deficit = conditions.compound.pace_offset() + wear_rate * conditions.tyre_age as f32;
+
+    let fuel_deficit = conditions.fuel_kg * FUEL_EFFECT_PER_KG;
+
+    // Experience counts for most of the time lost finding grip off the racing line
+    let weather_deficit =
+        circuit.weather.lap_time_penalty() * (1.0 - driver.experience_level as f32 / 200.0);
+
+    circuit.base_lap_time
+        + driver_deficit
+        + car_deficit
+        + setup_deficit
+        + tyre_deficit
+        + fuel_deficit
+        + weather_deficit
+}
+
+/// Lap time in seconds including random variation. Less consistent drivers vary more, and
+/// everyone varies more in the wet.
+pub fn lap_time(
+    driver: &Driver,
+    car: &Car,
+    circuit: &Circuit,
+    setup: &CarSetup,
+    conditions: &LapConditions,
+    rng: &mut impl Rng,
+) -> f32 {
+    let spread = ((100 - driver.consistency_level.min(100)) as f32 / 100.0 * 0.8 + 0.05)
+        * circuit.weather.spread_factor();
+
+    ideal_lap_time(driver, car, circuit, setup, conditions)
+        + rng.random_range(-spread..spread)
+}
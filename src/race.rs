@@ -0,0 +1,706 @@
+use crate::error::SimulationError;
+use crate::models::{
+    Car, CarSetup, Circuit, Driver, DriverId, EngineMode, Flag, LapRecord, PitWallCall, RaceEvent,
+    RaceReport, RaceResult, TeamId, TeamOrder, TeamOrderKind, TeamOrderPolicy, TyreCompound,
+};
+use crate::pace::{FUEL_PER_LAP_KG, LapConditions, lap_time};
+use crate::utils::setup_for;
+use crate::world::World;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Seconds lost driving through the pit lane and changing tyres.
+pub const PIT_STOP_TIME_LOSS: f32 = 22.0;
+
+/// Gap in seconds under which two cars are close enough to fight for position.
+pub const BATTLE_GAP: f32 = 1.0;
+
+/// Laps a team order stays in force before the pit wall has to repeat it.
+const ORDER_DURATION_LAPS: u8 = 5;
+
+/// Chance that a retirement brings out the safety car.
+const SAFETY_CAR_CHANCE: f64 = 0.4;
+
+/// Laps the field spends behind the safety car.
+const SAFETY_CAR_LAPS: u8 = 3;
+
+/// Lap times behind the safety car, as a multiple of racing speed.
+const SAFETY_CAR_PACE: f32 = 1.35;
+
+/// Closest gap in seconds the field bunches up to behind the safety car.
+const SAFETY_CAR_GAP: f32 = 0.5;
+
+/// Teams bring a stop planned within this many laps forward to pit behind the safety car.
+const SAFETY_CAR_PIT_WINDOW: u8 = 10;
+
+/// A car and everything the race engine tracks about it.
+#[derive(Debug, Clone)]
+pub struct RaceCar {
+    pub driver: Driver,
+    pub team: TeamId,
+    pub car: Car,
+    pub setup: CarSetup,
+    pub total_time: f32,
+    pub last_lap_time: f32,
+    pub laps_completed: u8,
+    pub compound: TyreCompound,
+    pub tyre_age: u8,
+    pub fuel_kg: f32,
+    pub pit_stops: u8,
+    pub planned_stops: Vec<(u8, TyreCompound)>, // (lap, compound to fit)
+    pub engine_mode: EngineMode,
+    pub retired: bool,
+}
+
+/// A team order that is currently being followed (or ignored) on track.
+#[derive(Debug, Clone)]
+struct ActiveOrder {
+    order: TeamOrder,
+    until_lap: u8,
+}
+
+/// Lap-by-lap race simulation. Cars race on track, pit according to their strategy, battle
+/// for position when close, and follow (or ignore) their team's orders against teammates.
+/// All randomness comes from the race's own seeded generator.
+#[derive(Debug, Clone)]
+pub struct RaceSimulation {
+    pub circuit: Circuit,
+    pub lap: u8,
+    pub cars: Vec<RaceCar>,
+    pub policies: Vec<(TeamId, TeamOrderPolicy)>,
+    pub team_orders: Vec<TeamOrder>,
+    pub laps: Vec<LapRecord>,
+    pub safety_car_until: Option<u8>, // last lap behind the safety car
+    pub pit_wall_team: Option<TeamId>, // run by the player in race-engineer mode
+    active_orders: Vec<ActiveOrder>,
+    events: Vec<RaceEvent>, // not yet collected with `take_events`
+    rng: StdRng,
+}
+
+impl RaceSimulation {
+    /// Lines the cars up in grid order. Drivers missing from `grid` start at the back, and
+    /// everything left to chance is drawn from `rng`. Fails if a driver has no team to race for.
+    pub fn new(
+        world: &World,
+        drivers: &[DriverId],
+        circuit: &Circuit,
+        setups: &[(DriverId, CarSetup)],
+        grid: &[RaceResult],
+        mut rng: StdRng,
+    ) -> Result<RaceSimulation, SimulationError> {
+        let mut ordered: Vec<DriverId> = drivers.to_vec();
+        ordered.sort_by_key(|driver| {
+            grid.iter()
+                .position(|result| result.driver == *driver)
+                .unwrap_or(usize::MAX)
+        });
+
+        let race_cars = ordered
+            .into_iter()
+            .enumerate()
+            .map(|(grid_index, id)| {
+                let driver = world.driver(id);
+                let team = world.team_of(id)?;
+                let car = world.car_of(id)?.clone();
+
+                let (compound, planned_stops) = pick_strategy(circuit.laps, &mut rng);
+
+                Ok(RaceCar {
+                    driver: driver.clone(),
+                    team,
+                    car,
+                    setup: setup_for(setups, id),
+                    // Cars further back cross the start line later
+                    total_time: grid_index as f32 * 0.25,
+                    last_lap_time: 0.0,
+                    laps_completed: 0,
+                    compound,
+                    tyre_age: 0,
+                    fuel_kg: circuit.laps as f32 * FUEL_PER_LAP_KG + 2.0,
+                    pit_stops: 0,
+                    planned_stops,
+                    engine_mode: EngineMode::Standard,
+                    retired: false,
+                })
+            })
+            .collect::<Result<Vec<RaceCar>, SimulationError>>()?;
+
+        Ok(RaceSimulation {
+            circuit: circuit.clone(),
+            lap: 0,
+            cars: race_cars,
+            policies: world
+                .teams()
+                .iter()
+                .map(|team| (team.id, team.order_policy.clone()))
+                .collect(),
+            team_orders: Vec::new(),
+            laps: Vec::new(),
+            safety_car_until: None,
+            pit_wall_team: None,
+            active_orders: Vec::new(),
+            events: vec![RaceEvent::Flag {
+                lap: 0,
+                flag: Flag::Green,
+            }],
+            rng,
+        })
+    }
+
+    /// Whether the lap being run (or the one just run) is behind the safety car.
+    pub fn safety_car_out(&self) -> bool {
+        self.safety_car_until.is_some_and(|until| self.lap <= until)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.lap >= self.circuit.laps || self.cars.iter().all(|car| car.retired)
+    }
+
+    /// Indices of running cars in track order, leader first.
+    pub fn running_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.cars.len())
+            .filter(|&index| !self.cars[index].retired)
+            .collect();
+        order.sort_by(|&a, &b| self.cars[a].total_time.total_cmp(&self.cars[b].total_time));
+        order
+    }
+
+    /// Events that happened since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<RaceEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn simulate_lap(&mut self) {
+        self.lap += 1;
+
+        let order = self.running_order();
+        let start_times: Vec<f32> = self.cars.iter().map(|car| car.total_time).collect();
+        let mut pitted = Vec::new();
+        let driven: Vec<TyreCompound> = self.cars.iter().map(|car| car.compound).collect();
+        let safety_car = self.safety_car_out();
+        let mut retirements = 0;
+
+        // Everyone drives their lap
+        for &index in &order {
+            let race_car = &mut self.cars[index];
+
+            // Spread the chance of a DNF over the whole race distance
+            let dnf_chance = race_car.driver.chance_of_dnf(&race_car.car) * 0.5
+                / self.circuit.laps as f32
+                * race_car.engine_mode.failure_factor();
+            if self.rng.random_bool(dnf_chance.min(1.0) as f64) {
+                race_car.retired = true;
+                self.events.push(RaceEvent::Retirement {
+                    lap: self.lap,
+                    driver: race_car.driver.id,
+                });
+                retirements += 1;
+                continue;
+            }
+
+            let conditions = LapConditions {
+                compound: race_car.compound,
+                tyre_age: race_car.tyre_age,
+                fuel_kg: race_car.fuel_kg,
+            };
+            let mut time = lap_time(
+                &race_car.driver,
+                &race_car.car,
+                &self.circuit,
+                &race_car.setup,
+                &conditions,
+                &mut self.rng,
+            ) + race_car.engine_mode.lap_time_offset();
+            if safety_car {
+                time *= SAFETY_CAR_PACE;
+            }
+
+            let pit_now = race_car
+                .planned_stops
+                .iter()
+                .position(|(lap, _)| *lap == self.lap);
+            if let Some(stop) = pit_now {
+                let (_, compound) = race_car.planned_stops.remove(stop);
+                // The field is slower behind the safety car, so less time is lost in the pits
+                time += if safety_car {
+                    PIT_STOP_TIME_LOSS / 2.0
+                } else {
+                    PIT_STOP_TIME_LOSS
+                };
+                race_car.compound = compound;
+                race_car.tyre_age = 0;
+                race_car.pit_stops += 1;
+                pitted.push(index);
+                self.events.push(RaceEvent::PitStop {
+                    lap: self.lap,
+                    driver: race_car.driver.id,
+                    compound,
+                    stop: race_car.pit_stops,
+                });
+            } else {
+                race_car.tyre_age += 1;
+            }
+
+            race_car.total_time += time;
+            race_car.last_lap_time = time;
+            race_car.laps_completed += 1;
+            race_car.fuel_kg = (race_car.fuel_kg - FUEL_PER_LAP_KG).max(0.0);
+        }
+
+        if retirements > 0 {
+            self.flag_retirement();
+        }
+
+        self.active_orders
+            .retain(|active| active.until_lap >= self.lap);
+
+        let order: Vec<usize> = order
+            .into_iter()
+            .filter(|&index| !self.cars[index].retired)
+            .collect();
+        if safety_car {
+            self.bunch_up();
+        } else {
+            self.resolve_battles(&order, &start_times);
+        }
+
+        self.record_lap_events(&order, &pitted, &driven);
+
+        if self.safety_car_until == Some(self.lap) && !self.is_finished() {
+            self.safety_car_until = None;
+            self.events.push(RaceEvent::Flag {
+                lap: self.lap,
+                flag: Flag::Green,
+            });
+        }
+    }
+
+    /// Fights for position between cars that ended the lap close together, front to back.
+    fn resolve_battles(&mut self, order: &[usize], start_times: &[f32]) {
+        for pair in order.windows(2) {
+            let (ahead, behind) = (pair[0], pair[1]);
+            let gap_before = start_times[behind] - start_times[ahead];
+            if gap_before > BATTLE_GAP {
+                continue;
+            }
+
+            let teammates = self.cars[ahead].team == self.cars[behind].team;
+            if teammates && self.apply_team_orders(ahead, behind) {
+                continue;
+            }
+
+            // The car behind was quicker but still has to make the move stick
+            if self.cars[behind].total_time < self.cars[ahead].total_time {
+                let pace_delta = self.cars[ahead].last_lap_time - self.cars[behind].last_lap_time;
+                let aggression = self.cars[behind].driver.aggression_level as f32;
+                let success_chance =
+                    (0.3 + pace_delta * 0.4 + (aggression - 70.0) / 100.0).clamp(0.1, 0.9);
+
+                if !self.rng.random_bool(success_chance as f64) {
+                    self.cars[behind].total_time = self.cars[ahead].total_time + 0.2;
+                }
+            }
+        }
+    }
+
+    /// Behind the safety car nobody passes and every gap halves, down to a car length or two.
+    fn bunch_up(&mut self) {
+        let order = self.running_order();
+        for pair in order.windows(2) {
+            let (ahead, behind) = (pair[0], pair[1]);
+            let gap = self.cars[behind].total_time - self.cars[ahead].total_time;
+            self.cars[behind].total_time =
+                self.cars[ahead].total_time + (gap / 2.0).max(SAFETY_CAR_GAP).min(gap);
+        }
+    }
+
+    /// Waves the yellow flag for a retirement, or sends out the safety car. The teams on
+    /// automatic strategy bring their next stop forward to make the most of it.
+    fn flag_retirement(&mut self) {
+        if self.safety_car_until.is_some()
+            || self.is_finished()
+            || !self.rng.random_bool(SAFETY_CAR_CHANCE)
+        {
+            self.events.push(RaceEvent::Flag {
+                lap: self.lap,
+                flag: Flag::Yellow,
+            });
+            return;
+        }
+
+        self.safety_car_until = Some(self.lap.saturating_add(SAFETY_CAR_LAPS));
+        self.events.push(RaceEvent::Flag {
+            lap: self.lap,
+            flag: Flag::SafetyCar,
+        });
+
+        let next_lap = self.lap + 1;
+        for race_car in &mut self.cars {
+            if race_car.retired || Some(race_car.team) == self.pit_wall_team {
+                continue;
+            }
+            if let Some(stop) = race_car
+                .planned_stops
+                .iter_mut()
+                .find(|(lap, _)| *lap <= self.lap.saturating_add(SAFETY_CAR_PIT_WINDOW))
+            {
+                stop.0 = next_lap;
+            }
+        }
+    }
+
+    /// Logs the passes made on track this lap and everyone's completed lap, as events and in the
+    /// lap history. Places lost in the pit lane don't count as overtakes.
+    fn record_lap_events(
+        &mut self,
+        order_before: &[usize],
+        pitted: &[usize],
+        driven: &[TyreCompound],
+    ) {
+        let order = self.running_order();
+
+        for (position, &index) in order.iter().enumerate() {
+            let was_ahead = &order_before[..order_before
+                .iter()
+                .position(|&other| other == index)
+                .unwrap_or(0)];
+
+            for &passed in order[position + 1..]
+                .iter()
+                .filter(|other| was_ahead.contains(other) && !pitted.contains(other))
+            {
+                self.events.push(RaceEvent::Overtake {
+                    lap: self.lap,
+                    driver: self.cars[index].driver.id,
+                    passed: self.cars[passed].driver.id,
+                    position: (position + 1) as u8,
+                });
+            }
+        }
+
+        for (position, &index) in order.iter().enumerate() {
+            let race_car = &self.cars[index];
+            self.events.push(RaceEvent::LapCompleted {
+                session: "Race".to_string(),
+                lap: self.lap,
+                driver: race_car.driver.id,
+                position: Some((position + 1) as u8),
+                lap_time: race_car.last_lap_time,
+                total_time: Some(race_car.total_time),
+                compound: race_car.compound,
+            });
+            self.laps.push(LapRecord {
+                lap: self.lap,
+                driver: race_car.driver.id,
+                position: (position + 1) as u8,
+                lap_time: race_car.last_lap_time,
+                total_time: race_car.total_time,
+                compound: driven[index],
+                pitted: pitted.contains(&index),
+            });
+        }
+
+        if self.is_finished() {
+            self.events.push(RaceEvent::Flag {
+                lap: self.lap,
+                flag: Flag::Chequered,
+            });
+        }
+    }
+
+    /// Applies the team's policy to two teammates fighting on track. Returns true when an
+    /// order decided the outcome of the fight, false when they are left to race.
+    fn apply_team_orders(&mut self, ahead: usize, behind: usize) -> bool {
+        let team = self.cars[ahead].team;
+        let policy = self
+            .policies
+            .iter()
+            .find(|(id, _)| *id == team)
+            .map(|(_, policy)| policy.clone())
+            .unwrap_or(TeamOrderPolicy::FreeRacing);
+
+        let attacking = self.cars[behind].total_time < self.cars[ahead].total_time;
+        let behind_faster = self.cars[ahead].last_lap_time - self.cars[behind].last_lap_time > 0.3;
+
+        let kind = match &policy {
+            TeamOrderPolicy::FreeRacing => None,
+            TeamOrderPolicy::HoldPosition if attacking => Some(TeamOrderKind::HoldPosition),
+            TeamOrderPolicy::SwapForStrategy if behind_faster => {
+                Some(TeamOrderKind::LetTeammateThrough)
+            }
+            TeamOrderPolicy::NumberOneDriver(number_one) => {
+                if self.cars[behind].driver.id == *number_one {
+                    Some(TeamOrderKind::LetTeammateThrough)
+                } else if self.cars[ahead].driver.id == *number_one && attacking {
+                    Some(TeamOrderKind::HoldPosition)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        // An order from the player's pit wall stands until it runs out, whatever the policy
+        let (ahead_id, behind_id) = (self.cars[ahead].driver.id, self.cars[behind].driver.id);
+        let kind = kind.or_else(|| {
+            self.active_orders
+                .iter()
+                .map(|active| &active.order)
+                .find(|order| match order.kind {
+                    TeamOrderKind::HoldPosition => {
+                        attacking && order.driver == behind_id && order.teammate == ahead_id
+                    }
+                    TeamOrderKind::LetTeammateThrough => {
+                        order.driver == ahead_id && order.teammate == behind_id
+                    }
+                })
+                .map(|order| order.kind)
+        });
+        let Some(kind) = kind else {
+            return false;
+        };
+
+        // Hold orders go to the driver behind, swap orders to the driver ahead
+        let (receiver, teammate) = match kind {
+            TeamOrderKind::HoldPosition => (behind, ahead),
+            TeamOrderKind::LetTeammateThrough => (ahead, behind),
+        };
+        let receiver_id = self.cars[receiver].driver.id;
+
+        let obeyed = match self
+            .active_orders
+            .iter()
+            .find(|active| active.order.driver == receiver_id && active.order.kind == kind)
+        {
+            // Already told, the driver sticks to their earlier decision
+            Some(active) => active.order.obeyed,
+            None => {
+                let order = TeamOrder {
+                    lap: self.lap,
+                    team,
+                    kind,
+                    driver: receiver_id,
+                    teammate: self.cars[teammate].driver.id,
+                    obeyed: !self.cars[receiver].driver.ignores_team_order(&mut self.rng),
+                };
+                let obeyed = order.obeyed;
+
+                self.team_orders.push(order.clone());
+                self.events.push(RaceEvent::TeamOrder {
+                    order: order.clone(),
+                });
+                self.active_orders.push(ActiveOrder {
+                    order,
+                    until_lap: self.lap.saturating_add(ORDER_DURATION_LAPS),
+                });
+                obeyed
+            }
+        };
+
+        if !obeyed {
+            return false;
+        }
+
+        match kind {
+            TeamOrderKind::HoldPosition => {
+                self.cars[behind].total_time = self.cars[ahead].total_time + 0.3;
+            }
+            TeamOrderKind::LetTeammateThrough => {
+                // Moving over costs the driver ahead a little time
+                let swapped_time = self.cars[behind]
+                    .total_time
+                    .min(self.cars[ahead].total_time);
+                self.cars[behind].total_time = swapped_time;
+                self.cars[ahead].total_time = swapped_time + 0.3;
+            }
+        }
+
+        true
+    }
+
+    /// A copy of the race as it stands, to continue down a different path. With a `seed` the
+    /// copy gets its own luck from here on, without one it plays out exactly like the original.
+    pub fn fork(&self, seed: Option<u64>) -> RaceSimulation {
+        let mut fork = self.clone();
+        if let Some(seed) = seed {
+            fork.rng = StdRng::seed_from_u64(seed);
+        }
+
+        fork
+    }
+
+    /// Replaces a driver's remaining pit stops with a single stop on `lap` for `compound`.
+    /// Returns false if the driver isn't in the race or `lap` has already been run.
+    pub fn change_pit_stop(&mut self, driver: DriverId, lap: u8, compound: TyreCompound) -> bool {
+        if lap <= self.lap {
+            return false;
+        }
+
+        match self.cars.iter_mut().find(|car| car.driver.id == driver) {
+            Some(race_car) => {
+                race_car.planned_stops = vec![(lap, compound)];
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Hands `team`'s pit wall to the player. Its cars keep their planned stops but no longer
+    /// react to the safety car, and only get the team orders the player gives.
+    pub fn take_pit_wall(&mut self, team: TeamId) {
+        self.pit_wall_team = Some(team);
+        for (id, policy) in &mut self.policies {
+            if *id == team {
+                *policy = TeamOrderPolicy::FreeRacing;
+            }
+        }
+    }
+
+    fn running_car_mut(&mut self, driver: DriverId) -> Option<&mut RaceCar> {
+        self.cars
+            .iter_mut()
+            .find(|race_car| race_car.driver.id == driver && !race_car.retired)
+    }
+
+    /// Calls `driver` in at the end of the next lap for `compound`, in place of their next
+    /// planned stop. Returns false if the driver isn't running.
+    pub fn box_driver(&mut self, driver: DriverId, compound: TyreCompound) -> bool {
+        let lap = self.lap + 1;
+        let Some(race_car) = self.running_car_mut(driver) else {
+            return false;
+        };
+        if !race_car.planned_stops.is_empty() {
+            race_car.planned_stops.remove(0);
+        }
+        race_car.planned_stops.insert(0, (lap, compound));
+
+        self.pit_wall_call(driver, PitWallCall::Box { compound });
+        true
+    }
+
+    /// Drops `driver`'s next planned stop. Returns false if they aren't running or have no stop
+    /// left to skip.
+    pub fn stay_out(&mut self, driver: DriverId) -> bool {
+        let Some(race_car) = self.running_car_mut(driver) else {
+            return false;
+        };
+        if race_car.planned_stops.is_empty() {
+            return false;
+        }
+        race_car.planned_stops.remove(0);
+
+        self.pit_wall_call(driver, PitWallCall::StayOut);
+        true
+    }
+
+    /// Returns false if the driver isn't running.
+    pub fn set_engine_mode(&mut self, driver: DriverId, mode: EngineMode) -> bool {
+        let Some(race_car) = self.running_car_mut(driver) else {
+            return false;
+        };
+        race_car.engine_mode = mode;
+
+        self.pit_wall_call(driver, PitWallCall::EngineMode { mode });
+        true
+    }
+
+    /// Orders `driver` to hold position behind or let through their teammate whenever the two
+    /// fight over the next laps. The driver decides straight away whether to listen. Returns
+    /// the order, or `None` when either of them isn't running.
+    pub fn give_team_order(&mut self, driver: DriverId, kind: TeamOrderKind) -> Option<TeamOrder> {
+        let receiver = self
+            .cars
+            .iter()
+            .position(|race_car| race_car.driver.id == driver && !race_car.retired)?;
+        let team = self.cars[receiver].team;
+        let teammate = self.cars.iter().find(|race_car| {
+            race_car.team == team && race_car.driver.id != driver && !race_car.retired
+        })?;
+
+        let order = TeamOrder {
+            lap: self.lap,
+            team,
+            kind,
+            driver,
+            teammate: teammate.driver.id,
+            obeyed: !self.cars[receiver].driver.ignores_team_order(&mut self.rng),
+        };
+        self.active_orders
+            .retain(|active| active.order.driver != driver);
+        self.team_orders.push(order.clone());
+        self.events.push(RaceEvent::TeamOrder {
+            order: order.clone(),
+        });
+        self.active_orders.push(ActiveOrder {
+            order: order.clone(),
+            until_lap: self.lap.saturating_add(ORDER_DURATION_LAPS),
+        });
+
+        Some(order)
+    }
+
+    fn pit_wall_call(&mut self, driver: DriverId, call: PitWallCall) {
+        self.events.push(RaceEvent::PitWallCall {
+            lap: self.lap,
+            driver,
+            call,
+        });
+    }
+
+    /// Final classification: finishers by total time, then retirements by distance covered.
+    pub fn into_report(self) -> RaceReport {
+        let mut cars = self.cars;
+        cars.sort_by(|a, b| match (a.retired, b.retired) {
+            (false, false) => a.total_time.total_cmp(&b.total_time),
+            (true, true) => b.laps_completed.cmp(&a.laps_completed),
+            (true, false) => std::cmp::Ordering::Greater,
+            (false, true) => std::cmp::Ordering::Less,
+        });
+
+        let results = cars
+            .iter()
+            .enumerate()
+            .map(|(position, race_car)| {
+                RaceResult::new(
+                    race_car.driver.id,
+                    race_car.team,
+                    (position + 1) as u8,
+                    race_car.total_time,
+                    race_car.retired,
+                )
+            })
+            .collect();
+
+        RaceReport {
+            results,
+            team_orders: self.team_orders,
+            laps: self.laps,
+        }
+    }
+}
+
+/// Picks a starting compound and the planned pit stops for a car.
+fn pick_strategy(laps: u8, rng: &mut impl Rng) -> (TyreCompound, Vec<(u8, TyreCompound)>) {
+    let lap_at = |fraction: f32| (laps as f32 * fraction).round().max(1.0) as u8;
+    let roll: f32 = rng.random_range(0.0..1.0);
+
+    if roll < 0.5 {
+        // One stop, mediums to hards
+        let stop = lap_at(rng.random_range(0.35..0.55));
+        (TyreCompound::Medium, vec![(stop, TyreCompound::Hard)])
+    } else if roll < 0.8 {
+        // One stop, softs to hards
+        let stop = lap_at(rng.random_range(0.25..0.4));
+        (TyreCompound::Soft, vec![(stop, TyreCompound::Hard)])
+    } else {
+        // Two stops on the softer compounds
+        let first = lap_at(rng.random_range(0.25..0.35));
+        let second = lap_at(rng.random_range(0.6..0.7));
+        (
+            TyreCompound::Soft,
+            vec![
+                (first, TyreCompound::Medium),
+                (second, TyreCompound::Medium),
+            ],
+        )
+    }
+}
@@ -0,0 +1,318 @@
+use crate::models::{DriverId, TeamId};
+use crate::world::World;
+use std::io::IsTerminal;
+
+/// What the terminal gets to see. Colour is only used on a terminal that can show it, and
+/// plain output drops the emoji as well for logs and CI.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Style {
+    Plain,
+    NoColour,
+    Ansi256,
+    TrueColour,
+}
+
+impl Style {
+    /// Picks the richest style the terminal supports, honouring `NO_COLOR`.
+    pub fn detect(plain: bool) -> Style {
+        let colour_term = std::env::var("COLORTERM").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+
+        if plain {
+            Style::Plain
+        } else if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+            || !std::io::stdout().is_terminal()
+            || term == "dumb"
+        {
+            Style::NoColour
+        } else if colour_term == "truecolor" || colour_term == "24bit" {
+            Style::TrueColour
+        } else {
+            Style::Ansi256
+        }
+    }
+
+    /// This style for output that isn't going to a terminal, like a file: no colour, and still
+    /// no emoji when plain.
+    pub fn uncoloured(self) -> Style {
+        match self {
+            Style::Plain => Style::Plain,
+            _ => Style::NoColour,
+        }
+    }
+
+    fn colour_enabled(self) -> bool {
+        matches!(self, Style::Ansi256 | Style::TrueColour)
+    }
+
+    /// Text in a team colour given as `RRGGBB`. Falls back to the nearest of the 256 standard
+    /// colours where 24-bit colour isn't available, and to plain text without colour or with a
+    /// colour that doesn't parse.
+    pub fn paint(self, text: &str, hex: &str) -> String {
+        let rgb = u32::from_str_radix(hex.trim_start_matches('#'), 16)
+            .ok()
+            .filter(|_| hex.trim_start_matches('#').len() == 6)
+            .map(|value| ((value >> 16) as u8, (value >> 8) as u8, value as u8));
+
+        match (self, rgb) {
+            (Style::TrueColour, Some((r, g, b))) => {
+                format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, text)
+            }
+            (Style::Ansi256, Some((r, g, b))) => {
+                let level = |channel: u8| (channel as u16 * 5 + 127) / 255;
+                let index = 16 + 36 * level(r) + 6 * level(g) + level(b);
+                format!("\x1b[38;5;{}m{}\x1b[0m", index, text)
+            }
+            _ => text.to_string(),
+        }
+    }
+
+    pub fn bold(self, text: &str) -> String {
+        if self.colour_enabled() {
+            format!("\x1b[1m{}\x1b[0m", text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Output as it should be printed in this style: emoji are dropped in plain mode, together
+    /// with the space that separated them from the text.
+    pub fn clean(self, text: String) -> String {
+        if self != Style::Plain {
+            return text;
+        }
+
+        let mut cleaned = String::with_capacity(text.len());
+        let mut skip_space = false;
+        for c in text.chars() {
+            if is_emoji(c) {
+                skip_space = cleaned.is_empty() || cleaned.ends_with([' ', '\n']);
+                continue;
+            }
+            if !(c == ' ' && skip_space) {
+                cleaned.push(c);
+            }
+            skip_space = false;
+        }
+
+        let mut lines: Vec<&str> = cleaned.split('\n').map(str::trim_end).collect();
+        if lines.last() == Some(&"") && lines.len() > 1 && !text.ends_with('\n') {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF | 0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0xFE0F | 0x200D
+    )
+}
+
+/// Formats seconds as `m:ss.sss`, or `h:mm:ss.sss` for race distances.
+pub fn format_time(seconds: f32) -> String {
+    let total_millis = (seconds * 1000.0).round() as u64;
+    let (hours, rest) = (total_millis / 3_600_000, total_millis % 3_600_000);
+    let (minutes, rest) = (rest / 60_000, rest % 60_000);
+    let (secs, millis) = (rest / 1000, rest % 1000);
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}.{:03}", hours, minutes, secs, millis)
+    } else {
+        format!("{}:{:02}.{:03}", minutes, secs, millis)
+    }
+}
+
+/// A gap in seconds as it appears on a timing screen.
+pub fn format_gap(seconds: f32) -> String {
+    format!("+{:.3}", seconds)
+}
+
+/// The colour bar, acronym, name and team cells that identify a driver in a timing table.
+pub fn driver_cells(world: &World, driver: DriverId, team: TeamId) -> Vec<Cell> {
+    let colour = &world.team(team).team_color;
+
+    vec![
+        Cell::bar(colour),
+        Cell::team(world.driver(driver).name_acronym.clone(), colour),
+        Cell::new(world.driver_name(driver)),
+        Cell::new(world.team_name(team)),
+    ]
+}
+
+/// One cell of a table, optionally in a team colour and bold.
+#[derive(Debug, Clone, Default)]
+pub struct Cell {
+    text: String,
+    colour: Option<String>,
+    bold: bool,
+    bar: bool,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>) -> Cell {
+        Cell {
+            text: text.into(),
+            ..Cell::default()
+        }
+    }
+
+    /// A driver acronym or team name in the team's colour.
+    pub fn team(text: impl Into<String>, colour: &str) -> Cell {
+        Cell {
+            text: text.into(),
+            colour: Some(colour.to_string()),
+            bold: true,
+            bar: false,
+        }
+    }
+
+    /// Text in a colour without making it bold, like a tyre compound.
+    pub fn coloured(text: impl Into<String>, colour: &str) -> Cell {
+        Cell {
+            text: text.into(),
+            colour: Some(colour.to_string()),
+            bold: false,
+            bar: false,
+        }
+    }
+
+    /// The team colour bar leading a row. It takes no space without colour.
+    pub fn bar(colour: &str) -> Cell {
+        Cell {
+            text: "▌".to_string(),
+            colour: Some(colour.to_string()),
+            bold: false,
+            bar: true,
+        }
+    }
+
+    /// The text as it shows in `style`.
+    fn text(&self, style: Style) -> &str {
+        if self.bar && !style.colour_enabled() {
+            ""
+        } else {
+            &self.text
+        }
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Cell {
+        Cell::new(text)
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Cell {
+        Cell::new(text)
+    }
+}
+
+/// A table whose columns line up however long the names in them are. Numeric columns can be
+/// aligned right so times and gaps line up on the decimal point.
+#[derive(Debug, Clone)]
+pub struct Table {
+    headers: Vec<String>,
+    right_aligned: Vec<bool>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Table {
+        Table {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            right_aligned: vec![false; headers.len()],
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn align_right(mut self, columns: &[usize]) -> Table {
+        for &column in columns {
+            if let Some(aligned) = self.right_aligned.get_mut(column) {
+                *aligned = true;
+            }
+        }
+        self
+    }
+
+    pub fn row(&mut self, cells: Vec<Cell>) {
+        self.rows.push(cells);
+    }
+
+    pub fn render(&self, style: Style) -> String {
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|column| {
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| cell.text(style).chars().count())
+                    .chain([self.headers[column].chars().count()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        // Columns with nothing in them, like the colour bar without colour, are left out
+        let columns: Vec<usize> = (0..self.headers.len())
+            .filter(|&column| widths[column] > 0)
+            .collect();
+
+        let line = |cells: Vec<(String, Option<&Cell>)>| {
+            let padded: Vec<String> = cells
+                .into_iter()
+                .zip(&columns)
+                .map(|((text, cell), &column)| {
+                    let padding = " ".repeat(widths[column] - text.chars().count());
+                    let text = match cell {
+                        Some(Cell {
+                            colour: Some(colour),
+                            ..
+                        }) => style.paint(&text, colour),
+                        _ => text,
+                    };
+                    let text = match cell {
+                        Some(Cell { bold: true, .. }) => style.bold(&text),
+                        _ => text,
+                    };
+
+                    if self.right_aligned[column] {
+                        format!("{}{}", padding, text)
+                    } else {
+                        format!("{}{}", text, padding)
+                    }
+                })
+                .collect();
+
+            padded.join("  ").trim_end().to_string()
+        };
+
+        let header = line(
+            columns
+                .iter()
+                .map(|&column| (self.headers[column].clone(), None))
+                .collect(),
+        );
+        let mut lines = vec![style.bold(&header)];
+        lines.push(
+            columns
+                .iter()
+                .map(|&column| "-".repeat(widths[column]))
+                .collect::<Vec<_>>()
+                .join("  "),
+        );
+        for row in &self.rows {
+            lines.push(line(
+                columns
+                    .iter()
+                    .map(|&column| match row.get(column) {
+                        Some(cell) => (cell.text(style).to_string(), Some(cell)),
+                        None => (String::new(), None),
+                    })
+                    .collect(),
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
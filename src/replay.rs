@@ -0,0 +1,447 @@
+use crate::models::{
+    DriverId, Entrant, Flag, PitWallCall, RaceEvent, RaceResult, TeamOrderKind, TyreCompound,
+};
+use crate::render::{Cell, Table, format_gap, format_time};
+use crate::reporter::Reporter;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+
+/// Everything needed to watch a weekend again: the race seed and every event in order.
+/// Rendering only reads the events, so a replay always shows exactly what happened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub circuit: String,
+    pub laps: u8,
+    pub events: Vec<RaceEvent>,
+}
+
+/// A driver's line on the timing tower at the end of a lap.
+#[derive(Debug, Clone, Serialize)]
+pub struct TowerRow {
+    pub position: u8,
+    pub driver: DriverId,
+    pub acronym: String,
+    pub team_name: String,
+    pub team_color: String,
+    pub gap: f32,      // to the leader
+    pub interval: f32, // to the car ahead
+    pub last_lap: f32,
+    pub compound: Option<TyreCompound>,
+    pub tyre_age: u8, // laps on the current set
+    pub pit_stops: u8,
+    pub retired_on: Option<u8>,
+}
+
+impl TowerRow {
+    /// The row's cells on the timing tower table, see `Replay::render`.
+    fn cells(&self) -> Vec<Cell> {
+        // On the grid nobody has a lap time yet, so there are no gaps to show
+        let on_track = self.retired_on.is_none() && self.last_lap > 0.0;
+        let gap = match self.retired_on {
+            Some(lap) => format!("OUT (lap {})", lap),
+            None if self.position == 1 && on_track => "Leader".to_string(),
+            None if on_track => format_gap(self.gap),
+            None => String::new(),
+        };
+        let interval = if self.position > 1 && on_track {
+            format_gap(self.interval)
+        } else {
+            String::new()
+        };
+        let last_lap = if self.last_lap > 0.0 {
+            format_time(self.last_lap)
+        } else {
+            String::new()
+        };
+
+        vec![
+            Cell::new(format!("P{}", self.position)),
+            Cell::bar(&self.team_color),
+            Cell::team(self.acronym.clone(), &self.team_color),
+            Cell::new(self.team_name.clone()),
+            Cell::new(gap),
+            Cell::new(interval),
+            Cell::new(last_lap),
+            match self.compound {
+                Some(compound) => Cell::coloured(
+                    format!("{} {:>2}", compound.short_name(), self.tyre_age),
+                    compound.colour(),
+                ),
+                None => Cell::new("-"),
+            },
+            Cell::new(self.pit_stops.to_string()),
+        ]
+    }
+}
+
+impl Replay {
+    /// Builds a replay from a weekend's recorded events. The circuit and distance come from
+    /// the race's start event.
+    pub fn new(seed: u64, events: Vec<RaceEvent>) -> Replay {
+        let (circuit, laps) = events
+            .iter()
+            .find_map(|event| match event {
+                RaceEvent::SessionStart {
+                    session,
+                    circuit,
+                    laps,
+                    ..
+                } if session == "Race" => Some((circuit.clone(), laps.unwrap_or(0))),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        Replay {
+            seed,
+            circuit,
+            laps,
+            events,
+        }
+    }
+
+    /// A replay of a race that is still being run, from its qualifying `grid` and `start`
+    /// event. The race's events are added to `events` as it goes on.
+    pub fn of_race(seed: u64, grid: &[RaceResult], start: RaceEvent) -> Replay {
+        let qualifying = RaceEvent::SessionEnd {
+            session: "Qualifying".to_string(),
+            classification: grid.to_vec(),
+        };
+
+        Replay::new(seed, vec![qualifying, start])
+    }
+
+    /// Loads a replay saved with `save`. Replays are easy to edit by hand, so one with a lap
+    /// time that is negative or not a number is turned away rather than played.
+    pub fn load(path: &str) -> Result<Replay, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        let replay: Replay = serde_json::from_str(&contents)?;
+
+        for event in &replay.events {
+            if let RaceEvent::LapCompleted {
+                session,
+                lap,
+                lap_time,
+                total_time,
+                ..
+            } = event
+            {
+                let valid = |time: f32| time.is_finite() && time >= 0.0;
+                if !valid(*lap_time) || !total_time.is_none_or(valid) {
+                    return Err(format!(
+                        "{} has an invalid time on lap {} of {}",
+                        path, lap, session
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(replay)
+    }
+
+    /// Saves the replay as a single line of JSON to keep the file small.
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string(self)?)?;
+
+        Ok(())
+    }
+
+    /// Events of the race itself, from its start event on.
+    fn race_events(&self) -> &[RaceEvent] {
+        let start = self
+            .events
+            .iter()
+            .position(|event| {
+                matches!(event, RaceEvent::SessionStart { session, .. } if session == "Race")
+            })
+            .unwrap_or(self.events.len());
+
+        &self.events[start..]
+    }
+
+    fn entrants(&self) -> &[Entrant] {
+        match self.race_events().first() {
+            Some(RaceEvent::SessionStart { entrants, .. }) => entrants,
+            _ => &[],
+        }
+    }
+
+    /// Starting order, taken from the qualifying classification.
+    fn grid(&self) -> Vec<DriverId> {
+        let qualifying = self.events.iter().find_map(|event| match event {
+            RaceEvent::SessionEnd {
+                session,
+                classification,
+            } if session == "Qualifying" => Some(classification),
+            _ => None,
+        });
+
+        let mut grid: Vec<DriverId> = self.entrants().iter().map(|e| e.driver).collect();
+        if let Some(qualifying) = qualifying {
+            grid.sort_by_key(|driver| {
+                qualifying
+                    .iter()
+                    .position(|result| result.driver == *driver)
+                    .unwrap_or(usize::MAX)
+            });
+        }
+
+        grid
+    }
+
+    /// The timing tower as it stood at the end of `lap`, lap 0 being the starting grid.
+    pub fn timing_tower(&self, lap: u8) -> Vec<TowerRow> {
+        let mut rows: Vec<TowerRow> = self
+            .grid()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, driver)| {
+                let entrant = self.entrants().iter().find(|e| e.driver == *driver)?;
+
+                Some(TowerRow {
+                    position: (index + 1) as u8,
+                    driver: *driver,
+                    acronym: entrant.acronym.clone(),
+                    team_name: entrant.team_name.clone(),
+                    team_color: entrant.team_color.clone(),
+                    gap: 0.0,
+                    interval: 0.0,
+                    last_lap: 0.0,
+                    compound: None,
+                    tyre_age: 0,
+                    pit_stops: 0,
+                    retired_on: None,
+                })
+            })
+            .collect();
+        let mut total_times = vec![0.0; rows.len()];
+        // A stop comes before the lap it ends, and that lap doesn't age the new set
+        let mut stopped_on = vec![0; rows.len()];
+
+        for event in self.race_events() {
+            let row_of = |driver: &DriverId| rows.iter().position(|row| row.driver == *driver);
+
+            match event {
+                RaceEvent::LapCompleted {
+                    lap: event_lap,
+                    driver,
+                    position,
+                    lap_time,
+                    total_time,
+                    compound,
+                    ..
+                } if *event_lap <= lap => {
+                    if let Some(index) = row_of(driver) {
+                        let row = &mut rows[index];
+                        row.position = position.unwrap_or(row.position);
+                        row.last_lap = *lap_time;
+                        row.compound = Some(*compound);
+                        if stopped_on[index] != *event_lap {
+                            row.tyre_age += 1;
+                        }
+                        total_times[index] = total_time.unwrap_or(0.0);
+                    }
+                }
+                RaceEvent::PitStop {
+                    lap: event_lap,
+                    driver,
+                    ..
+                } if *event_lap <= lap => {
+                    if let Some(index) = row_of(driver) {
+                        rows[index].pit_stops += 1;
+                        rows[index].tyre_age = 0;
+                        stopped_on[index] = *event_lap;
+                    }
+                }
+                RaceEvent::Retirement {
+                    lap: event_lap,
+                    driver,
+                } if *event_lap <= lap => {
+                    if let Some(index) = row_of(driver) {
+                        rows[index].retired_on = Some(*event_lap);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Running cars by position, then retirements with the latest one first
+        let mut order: Vec<usize> = (0..rows.len()).collect();
+        order.sort_by_key(|&index| match rows[index].retired_on {
+            None => (0, rows[index].position as i32),
+            Some(lap) => (1, -(lap as i32)),
+        });
+
+        let leader_time = order
+            .first()
+            .map(|&index| total_times[index])
+            .unwrap_or(0.0);
+        let mut previous_time = leader_time;
+        let mut tower = Vec::new();
+        for (position, index) in order.into_iter().enumerate() {
+            let mut row = rows[index].clone();
+            row.position = (position + 1) as u8;
+            if lap > 0 && row.retired_on.is_none() {
+                row.gap = total_times[index] - leader_time;
+                row.interval = total_times[index] - previous_time;
+                previous_time = total_times[index];
+            }
+            tower.push(row);
+        }
+
+        tower
+    }
+
+    /// Lines describing what happened on `lap`: passes, stops, retirements, flags and orders.
+    pub fn lap_summary(&self, lap: u8) -> Vec<String> {
+        let name = |driver: &DriverId| {
+            self.entrants()
+                .iter()
+                .find(|e| e.driver == *driver)
+                .map(|e| e.acronym.clone())
+                .unwrap_or_else(|| driver.to_string())
+        };
+
+        self.race_events()
+            .iter()
+            .filter(|event| event.lap() == Some(lap))
+            .filter_map(|event| describe_event(event, &name))
+            .collect()
+    }
+
+    /// The timing tower at the end of `lap` as a table, ready to render.
+    pub fn tower_table(&self, lap: u8) -> Table {
+        let mut table = Table::new(&[
+            "Pos", "", "Driver", "Team", "Gap", "Int", "Last Lap", "Tyre", "Stops",
+        ])
+        .align_right(&[4, 5, 6, 8]);
+        for row in self.timing_tower(lap) {
+            table.row(row.cells());
+        }
+
+        table
+    }
+
+    /// Shows the timing tower at the end of `lap` and what happened on it to `reporter`.
+    pub fn show(&self, lap: u8, reporter: &mut dyn Reporter) {
+        let title = if lap == 0 {
+            format!("🏁 {} - Starting Grid 🏁", self.circuit)
+        } else {
+            format!("🏁 {} - Lap {}/{} 🏁", self.circuit, lap, self.laps)
+        };
+        reporter.table(&title, &self.tower_table(lap));
+        for line in self.lap_summary(lap) {
+            reporter.note(&line);
+        }
+    }
+}
+
+/// A line for the race control feed about `event`, naming drivers with `name`. Laps and
+/// session starts and ends aren't worth a line.
+pub fn describe_event(event: &RaceEvent, name: &dyn Fn(&DriverId) -> String) -> Option<String> {
+    match event {
+        RaceEvent::Overtake {
+            driver,
+            passed,
+            position,
+            ..
+        } => Some(format!(
+            "⚔️ {} passes {} for P{}",
+            name(driver),
+            name(passed),
+            position
+        )),
+        RaceEvent::PitStop {
+            driver,
+            compound,
+            stop,
+            ..
+        } => Some(format!(
+            "🔧 {} pits (stop {}) for {}",
+            name(driver),
+            stop,
+            compound.name()
+        )),
+        RaceEvent::Retirement { driver, .. } => Some(format!("💥 {} retires", name(driver))),
+        RaceEvent::Flag {
+            flag: Flag::Yellow, ..
+        } => Some("🟨 Yellow flag".to_string()),
+        RaceEvent::Flag {
+            flag: Flag::SafetyCar,
+            ..
+        } => Some("🚨 Safety car deployed".to_string()),
+        RaceEvent::Flag {
+            lap,
+            flag: Flag::Green,
+        } if *lap > 0 => Some("🟩 Safety car in, green flag".to_string()),
+        RaceEvent::Flag {
+            flag: Flag::Chequered,
+            ..
+        } => Some("🏁 Chequered flag".to_string()),
+        RaceEvent::TeamOrder { order } => {
+            let instruction = match order.kind {
+                TeamOrderKind::HoldPosition => "hold position behind",
+                TeamOrderKind::LetTeammateThrough => "let through",
+            };
+            Some(format!(
+                "📻 {} told to {} {}{}",
+                name(&order.driver),
+                instruction,
+                name(&order.teammate),
+                if order.obeyed { "" } else { " - ORDER IGNORED" }
+            ))
+        }
+        RaceEvent::PitWallCall { driver, call, .. } => Some(match call {
+            PitWallCall::Box { compound } => {
+                format!("🎧 Pit wall: box {} for {}", name(driver), compound.name())
+            }
+            PitWallCall::StayOut => format!("🎧 Pit wall: {} stays out", name(driver)),
+            PitWallCall::EngineMode { mode } => {
+                format!("🎧 Pit wall: {} engine mode {}", name(driver), mode.name())
+            }
+        }),
+        _ => None,
+    }
+}
+
+/// Plays back a replay file from `start_lap` to `reporter`, with `ask` putting a question to
+/// the viewer and handing back the answer: Enter steps a lap, a number jumps to that lap and
+/// `q` quits.
+pub fn run_replay(
+    path: &str,
+    start_lap: u8,
+    reporter: &mut dyn Reporter,
+    ask: &mut dyn FnMut(&str) -> String,
+) -> Result<(), Box<dyn Error>> {
+    let replay = Replay::load(path)?;
+    reporter.note(&format!(
+        "📼 Replay of {} ({} laps, race seed {})",
+        replay.circuit, replay.laps, replay.seed
+    ));
+
+    let mut lap = start_lap.min(replay.laps);
+    loop {
+        replay.show(lap, reporter);
+
+        let answer = loop {
+            let answer = ask("[Enter] next lap, lap number to jump, q to quit:");
+            if answer.is_empty() || answer.eq_ignore_ascii_case("q") || answer.parse::<u8>().is_ok()
+            {
+                break answer;
+            }
+            reporter.note(&format!("❓ Unknown command `{}`", answer));
+        };
+        if answer.eq_ignore_ascii_case("q") {
+            break;
+        }
+        match answer.parse::<u8>() {
+            Ok(target) => lap = target.min(replay.laps),
+            Err(_) if lap >= replay.laps => break,
+            Err(_) => lap += 1,
+        }
+    }
+
+    Ok(())
+}
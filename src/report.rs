@@ -0,0 +1,301 @@
+use crate::career::add_points;
+use crate::export::WeekendResults;
+use crate::models::{DriverId, RaceResult, TeamId};
+use crate::render::format_time;
+use crate::season::Standings;
+use crate::utils::{championship_points, points_for_position};
+use crate::world::World;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+
+// Inline so the report is a single file that opens anywhere, even offline
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2em auto; max-width: 960px; color: #1d1d1f; }
+h1 { margin-bottom: 0; }
+h2 { margin-top: 2em; border-bottom: 2px solid #e10600; padding-bottom: 0.2em; }
+.subtitle { color: #6e6e73; margin-top: 0.2em; }
+table { border-collapse: collapse; width: 100%; margin: 0.5em 0 1em; font-size: 0.92em; }
+th, td { padding: 0.35em 0.6em; text-align: left; }
+th { background: #f5f5f7; }
+tr:nth-child(even) td { background: #fafafa; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+td.team { border-left: 6px solid; }
+.out td { color: #86868b; }
+.tyre { display: inline-block; padding: 0 0.5em; margin-right: 0.3em; border-radius: 1em; font-size: 0.85em; color: #1d1d1f; border: 2px solid; }
+.tyre.soft { border-color: #da291c; }
+.tyre.medium { border-color: #ffd12e; }
+.tyre.hard { border-color: #9a9a9a; }
+";
+
+/// Builds a self-contained HTML page for a weekend: every session classification in team
+/// colours, the qualifying knockouts, the race with gaps and retirements, everyone's tyre
+/// strategy and `championship`, the standings after the round, with what the round added.
+pub fn weekend_html(weekend: &WeekendResults, championship: &Standings) -> String {
+    let world = &weekend.world;
+    let mut html = String::new();
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{circuit} - Weekend Report</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>{circuit}</h1>\n<p class=\"subtitle\">Weekend report · race seed {seed}</p>\n",
+        circuit = escape(&weekend.circuit),
+        seed = weekend.seed
+    );
+
+    html.push_str("<h2>Race</h2>\n");
+    html.push_str(&race_table(world, weekend));
+
+    html.push_str("<h2>Strategy</h2>\n");
+    html.push_str(&strategy_table(world, weekend));
+
+    html.push_str("<h2>Qualifying</h2>\n");
+    let knockouts = [
+        ("Q3", 0..10),
+        ("Eliminated in Q2", 10..15),
+        ("Eliminated in Q1", 15..weekend.qualifying.len()),
+    ];
+    for (title, range) in knockouts {
+        let results = weekend.qualifying.get(range).unwrap_or_default();
+        let pole_time = weekend
+            .qualifying
+            .first()
+            .map(|result| result.finish_time_seconds);
+        let _ = writeln!(html, "<h3>{}</h3>", title);
+        html.push_str(&lap_time_table(world, results, pole_time));
+    }
+
+    for session in weekend.practice.iter().rev() {
+        let _ = writeln!(html, "<h2>{}</h2>", escape(&session.name));
+        let fastest = session
+            .results
+            .first()
+            .map(|result| result.finish_time_seconds);
+        html.push_str(&lap_time_table(world, &session.results, fastest));
+    }
+
+    html.push_str("<h2>Championship after the round</h2>\n");
+    html.push_str(&standings_tables(
+        world,
+        championship,
+        &weekend.race.results,
+    ));
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+pub fn write_weekend_report(
+    path: &str,
+    weekend: &WeekendResults,
+    championship: &Standings,
+) -> io::Result<()> {
+    fs::write(path, weekend_html(weekend, championship))
+}
+
+/// Race classification with the gap to the winner, pit stops and the lap of any retirement.
+fn race_table(world: &World, weekend: &WeekendResults) -> String {
+    let race = &weekend.race;
+    let winner_time = race
+        .results
+        .first()
+        .map(|result| result.finish_time_seconds)
+        .unwrap_or(0.0);
+
+    let mut html = table_header(&["Pos", "Driver", "Team", "Time / Gap", "Stops", "Points"]);
+    for result in &race.results {
+        let laps = race.laps_of(result.driver);
+        let stops = laps.iter().filter(|lap| lap.pitted).count();
+        let time = if result.dnf {
+            format!("DNF (lap {})", laps.len() + 1)
+        } else if result.finish_position == 1 {
+            format_time(result.finish_time_seconds)
+        } else {
+            format!("+{:.3}s", result.finish_time_seconds - winner_time)
+        };
+        let points = if result.dnf {
+            0
+        } else {
+            points_for_position(result.finish_position)
+        };
+
+        let _ = writeln!(
+            html,
+            "<tr{}><td>{}</td>{}{}<td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            if result.dnf { " class=\"out\"" } else { "" },
+            if result.dnf {
+                "DNF".to_string()
+            } else {
+                format!("P{}", result.finish_position)
+            },
+            driver_cell(world, result.driver),
+            team_cell(world, result.team),
+            time,
+            stops,
+            points
+        );
+    }
+    html.push_str("</table>\n");
+
+    html
+}
+
+/// Every driver's stints in race order, as coloured tyre chips with the laps run on them.
+fn strategy_table(world: &World, weekend: &WeekendResults) -> String {
+    let mut html = table_header(&["Driver", "Team", "Stints"]);
+
+    for result in &weekend.race.results {
+        let stints: Vec<String> = weekend
+            .race
+            .stints(result.driver)
+            .iter()
+            .map(|stint| {
+                format!(
+                    "<span class=\"tyre {}\">{} {}–{}</span>",
+                    stint.compound.name().to_lowercase(),
+                    stint.compound.short_name(),
+                    stint.first_lap,
+                    stint.last_lap
+                )
+            })
+            .collect();
+
+        let _ = writeln!(
+            html,
+            "<tr>{}{}<td>{}</td></tr>",
+            driver_cell(world, result.driver),
+            team_cell(world, result.team),
+            stints.join("")
+        );
+    }
+    html.push_str("</table>\n");
+
+    html
+}
+
+/// A classification by single lap time, with the gap to the fastest lap.
+fn lap_time_table(world: &World, results: &[RaceResult], fastest: Option<f32>) -> String {
+    let mut html = table_header(&["Pos", "Driver", "Team", "Lap Time", "Gap"]);
+
+    for result in results {
+        let gap = match fastest {
+            Some(fastest) if result.finish_time_seconds > fastest => {
+                format!("+{:.3}s", result.finish_time_seconds - fastest)
+            }
+            _ => String::new(),
+        };
+
+        let _ = writeln!(
+            html,
+            "<tr><td>P{}</td>{}{}<td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            result.finish_position,
+            driver_cell(world, result.driver),
+            team_cell(world, result.team),
+            format_time(result.finish_time_seconds),
+            gap
+        );
+    }
+    html.push_str("</table>\n");
+
+    html
+}
+
+/// Drivers' and constructors' championship standings, with the points scored this round next
+/// to the totals.
+fn standings_tables(
+    world: &World,
+    championship: &Standings,
+    race_results: &[RaceResult],
+) -> String {
+    let mut round_drivers: Vec<(DriverId, u32)> = Vec::new();
+    let mut round_teams: Vec<(TeamId, u32)> = Vec::new();
+    for (result, (driver, points)) in race_results.iter().zip(championship_points(race_results)) {
+        add_points(&mut round_drivers, driver, points);
+        add_points(&mut round_teams, result.team, points);
+    }
+
+    let mut html = String::from("<h3>Drivers</h3>\n");
+    html.push_str(&table_header(&[
+        "Pos",
+        "Driver",
+        "Team",
+        "Points",
+        "This round",
+    ]));
+    for row in championship.driver_rows(world) {
+        let Some(driver) = row.driver_id else {
+            continue;
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td>{}{}<td class=\"num\">{}</td><td class=\"num\">+{}</td></tr>",
+            row.position,
+            driver_cell(world, driver),
+            team_cell(world, row.team_id),
+            row.points,
+            scored(&round_drivers, driver)
+        );
+    }
+    html.push_str("</table>\n<h3>Constructors</h3>\n");
+
+    html.push_str(&table_header(&["Pos", "Team", "Points", "This round"]));
+    for row in championship.team_rows(world) {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td>{}<td class=\"num\">{}</td><td class=\"num\">+{}</td></tr>",
+            row.position,
+            team_cell(world, row.team_id),
+            row.points,
+            scored(&round_teams, row.team_id)
+        );
+    }
+    html.push_str("</table>\n");
+
+    html
+}
+
+/// What `id` scored in a round, nothing if it isn't in the round's points.
+fn scored<T: PartialEq>(round: &[(T, u32)], id: T) -> u32 {
+    round
+        .iter()
+        .find(|(other, _)| *other == id)
+        .map_or(0, |(_, points)| *points)
+}
+
+fn table_header(columns: &[&str]) -> String {
+    let cells: Vec<String> = columns
+        .iter()
+        .map(|column| format!("<th>{}</th>", column))
+        .collect();
+
+    format!("<table>\n<tr>{}</tr>\n", cells.join(""))
+}
+
+fn driver_cell(world: &World, driver: DriverId) -> String {
+    let driver = world.driver(driver);
+
+    format!(
+        "<td><strong>{}</strong> {}</td>",
+        escape(&driver.name_acronym),
+        escape(&driver.full_name)
+    )
+}
+
+/// A table cell with the team's colour down its left edge.
+fn team_cell(world: &World, team: TeamId) -> String {
+    let team = world.team(team);
+
+    format!(
+        "<td class=\"team\" style=\"border-left-color: #{}\">{}</td>",
+        escape(&team.team_color),
+        escape(&team.name)
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
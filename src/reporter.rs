@@ -0,0 +1,1109 @@
+use crate::export::{ClassificationRow, classification_rows};
+use crate::fork::{ForkOutcome, ForkRow};
+use crate::market::TransferMove;
+use crate::models::{Circuit, PracticeSession, RaceReport, RaceResult, Substitution, TeamId};
+use crate::montecarlo::MonteCarloRow;
+use crate::render::{Cell, Style, Table, driver_cells, format_gap, format_time};
+use crate::season::{StandingRow, Standings};
+use crate::simulator::WorldSource;
+use crate::utils::{points_for_position, sprint_points_for_position};
+use crate::world::World;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::error::Error;
+use std::io::Write;
+use std::ops::Range;
+
+/// Where the results of a weekend's sessions go. The simulation only hands back data, so
+/// embedding it prints nothing unless a reporter is asked to.
+pub trait Reporter {
+    /// Where the world came from, before anything is run with it.
+    fn world_loaded(&mut self, _world: &World, _source: &WorldSource) {}
+
+    fn entry_list(&mut self, _world: &World) {}
+
+    /// The circuit and any reserve drivers stepping in, before the first session.
+    fn weekend_start(&mut self, world: &World, circuit: &Circuit, substitutions: &[Substitution]);
+
+    fn practice_session(&mut self, world: &World, session: &PracticeSession);
+
+    /// Fuel- and compound-corrected race pace per team from the practice long runs.
+    fn race_pace(&mut self, _world: &World, _pace: &[(TeamId, f32)]) {}
+
+    fn qualifying(&mut self, world: &World, results: &[RaceResult]);
+
+    fn race(&mut self, world: &World, report: &RaceReport);
+
+    fn sprint(&mut self, world: &World, report: &RaceReport);
+
+    /// A what-if fork of the race next to the race as it was run.
+    fn fork(&mut self, _world: &World, _outcome: &ForkOutcome) {}
+
+    /// A season of `rounds` races is about to start.
+    fn season_start(&mut self, _rounds: usize, _seed: u64) {}
+
+    /// The race classification of one round of a season, numbered from 1.
+    fn round(
+        &mut self,
+        _world: &World,
+        _round: usize,
+        _circuit: &Circuit,
+        _results: &[RaceResult],
+    ) {
+    }
+
+    /// The drivers' and constructors' championships after a season.
+    fn standings(&mut self, _world: &World, _standings: &Standings) {}
+
+    /// The moves of a silly season, with `world` holding the line-ups they led to.
+    fn silly_season(&mut self, _world: &World, _moves: &[TransferMove]) {}
+
+    /// The grid every Monte Carlo race starts from, before the `runs` races are run.
+    fn monte_carlo_start(&mut self, _world: &World, _grid: &[RaceResult], _runs: u32, _seed: u64) {}
+
+    /// A status line from the front end, like where a file was written. Only meant for
+    /// people, so machine-readable formats leave it out.
+    fn note(&mut self, _message: &str) {}
+
+    /// A table from an interactive front end under `title`, like the timing tower of a replay.
+    /// Also only meant for people.
+    fn table(&mut self, _title: &str, _table: &Table) {}
+
+    /// How often every driver won, reached the podium or retired over `runs` races.
+    fn monte_carlo(&mut self, _world: &World, _rows: &[MonteCarloRow], _runs: u32) {}
+
+    /// Writes out whatever the reporter held back, once the weekend is over.
+    fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+/// The output formats a reporter can be created for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Terminal,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub fn from_name(name: &str) -> Option<ReportFormat> {
+        match name.to_lowercase().as_str() {
+            "terminal" | "text" => Some(ReportFormat::Terminal),
+            "json" => Some(ReportFormat::Json),
+            "csv" => Some(ReportFormat::Csv),
+            "markdown" | "md" => Some(ReportFormat::Markdown),
+            _ => None,
+        }
+    }
+
+    /// A reporter writing this format to `writer`. Only the terminal format uses `style`.
+    pub fn reporter(&self, writer: Box<dyn Write>, style: Style) -> Box<dyn Reporter> {
+        match self {
+            ReportFormat::Terminal => Box::new(TerminalReporter::new(writer, style)),
+            ReportFormat::Json => Box::new(JsonReporter::new(writer)),
+            ReportFormat::Csv => Box::new(CsvReporter::new(writer)),
+            ReportFormat::Markdown => Box::new(MarkdownReporter::new(writer)),
+        }
+    }
+}
+
+/// Writes one line of human-readable output to a terminal reporter, in its style.
+/// A terminal that has gone away is no reason to stop the simulation, so errors are ignored.
+macro_rules! tell {
+    ($reporter:expr) => {{
+        let _ = writeln!($reporter.writer);
+    }};
+    ($reporter:expr, $($arg:tt)*) => {{
+        let _ = writeln!($reporter.writer, "{}", $reporter.style.clean(format!($($arg)*)));
+    }};
+}
+
+/// Aligned timing tables in team colours, printed as each session ends.
+pub struct TerminalReporter {
+    writer: Box<dyn Write>,
+    style: Style,
+}
+
+impl TerminalReporter {
+    pub fn new(writer: Box<dyn Write>, style: Style) -> TerminalReporter {
+        TerminalReporter { writer, style }
+    }
+
+    pub fn stdout(style: Style) -> TerminalReporter {
+        TerminalReporter::new(Box::new(std::io::stdout()), style)
+    }
+
+    /// A race classification with the points `points` gives, and the team orders given.
+    fn race_classification(&mut self, world: &World, report: &RaceReport, points: fn(u8) -> u32) {
+        let mut table = Table::new(&[
+            "Pos", "", "Driver", "Name", "Team", "Time", "Int", "Stops", "Points",
+        ])
+        .align_right(&[5, 6, 7, 8]);
+        for (result, (time, interval, stops, points)) in
+            report.results.iter().zip(race_columns(report, points))
+        {
+            let mut cells = vec![Cell::new(race_position(result))];
+            cells.extend(driver_cells(world, result.driver, result.team));
+            cells.push(Cell::new(time));
+            cells.push(Cell::new(interval));
+            cells.push(Cell::new(stops.to_string()));
+            cells.push(Cell::new(points.to_string()));
+            table.row(cells);
+        }
+        tell!(self, "{}", table.render(self.style));
+
+        if !report.team_orders.is_empty() {
+            tell!(self, "\n--- Team Orders ---");
+            for order in &report.team_orders {
+                tell!(self, "{}", order.info(world));
+            }
+        }
+    }
+}
+
+impl Reporter for TerminalReporter {
+    fn world_loaded(&mut self, _world: &World, source: &WorldSource) {
+        match source {
+            WorldSource::Roster { season } => tell!(self, "📋 {} roster", season),
+            WorldSource::Generated { seed } => tell!(self, "🎲 Generated league, seed {}", seed),
+        }
+    }
+
+    fn entry_list(&mut self, world: &World) {
+        tell!(self, "\n🏁 Entry List 🏁");
+        let mut table = Table::new(&[
+            "",
+            "Team",
+            "Drivers",
+            "Reserves",
+            "Car",
+            "Rating",
+            "Team Orders",
+            "Budget",
+        ])
+        .align_right(&[5, 7]);
+
+        for team in world.teams() {
+            let acronyms = |drivers: &[_]| {
+                drivers
+                    .iter()
+                    .map(|driver| world.driver(*driver).name_acronym.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let car = world.car(team.car);
+
+            table.row(vec![
+                Cell::bar(&team.team_color),
+                Cell::team(team.name.clone(), &team.team_color),
+                Cell::new(acronyms(&team.drivers)),
+                Cell::new(acronyms(&team.reserve_drivers)),
+                Cell::new(car.name.clone()),
+                Cell::new(format!("{:.1}", car.average_rating())),
+                Cell::new(team.order_policy.name(world)),
+                Cell::new(format!("${:.1}M", team.driver_budget)),
+            ]);
+        }
+        tell!(self, "{}", table.render(self.style));
+    }
+
+    fn weekend_start(&mut self, world: &World, circuit: &Circuit, substitutions: &[Substitution]) {
+        tell!(self, "\n{}", circuit.info());
+        for substitution in substitutions {
+            tell!(self, "{}", substitution.info(world));
+        }
+    }
+
+    fn practice_session(&mut self, world: &World, session: &PracticeSession) {
+        tell!(self, "\n🏁 {} Summary 🏁", session.name);
+
+        tell!(self, "\n--- Runs ---");
+        let mut table = Table::new(&["", "Driver", "Name", "Team", "Run", "Best", "Lap Times"])
+            .align_right(&[5]);
+        for run in &session.runs {
+            let laps: Vec<String> = run.lap_times.iter().map(|t| format!("{:.3}", t)).collect();
+            let mut cells = driver_cells(world, run.driver, run.team);
+            cells.push(Cell::new(run.run_type.name()));
+            cells.push(Cell::new(format_time(run.best_lap())));
+            cells.push(Cell::new(laps.join(" ")));
+            table.row(cells);
+        }
+        tell!(self, "{}", table.render(self.style));
+
+        tell!(self, "\n--- Classification ({}) ---", session.name);
+        let mut table = Table::new(&[
+            "Pos", "", "Driver", "Name", "Team", "Best Lap", "Gap", "Int", "Setup",
+        ])
+        .align_right(&[5, 6, 7, 8]);
+        for (result, (gap, interval)) in session.results.iter().zip(timing_gaps(&session.results)) {
+            let quality = session
+                .setup_quality
+                .iter()
+                .find(|(driver, _)| *driver == result.driver)
+                .map(|(_, quality)| format!("{:.0}%", quality * 100.0))
+                .unwrap_or_default();
+
+            let mut cells = vec![Cell::new(format!("P{}", result.finish_position))];
+            cells.extend(driver_cells(world, result.driver, result.team));
+            cells.push(Cell::new(format_time(result.finish_time_seconds)));
+            cells.push(Cell::new(gap));
+            cells.push(Cell::new(interval));
+            cells.push(Cell::new(quality));
+            table.row(cells);
+        }
+        tell!(self, "{}", table.render(self.style));
+
+        if !session.long_runs.is_empty() {
+            tell!(self, "\n--- Long Run Pace ---");
+            let mut table = Table::new(&[
+                "Pos",
+                "",
+                "Driver",
+                "Name",
+                "Team",
+                "Tyre",
+                "Laps",
+                "Average",
+                "Fuel Corrected",
+            ])
+            .align_right(&[6, 7, 8]);
+            for (position, long_run) in session.long_runs.iter().enumerate() {
+                let mut cells = vec![Cell::new(format!("P{}", position + 1))];
+                cells.extend(driver_cells(world, long_run.driver, long_run.team));
+                cells.push(Cell::new(long_run.compound.name()));
+                cells.push(Cell::new(long_run.laps.to_string()));
+                cells.push(Cell::new(format_time(long_run.average_lap_time)));
+                cells.push(Cell::new(format_time(long_run.fuel_corrected_lap_time)));
+                table.row(cells);
+            }
+            tell!(self, "{}", table.render(self.style));
+        }
+    }
+
+    fn race_pace(&mut self, world: &World, pace: &[(TeamId, f32)]) {
+        tell!(self, "\n🏁 Predicted Race Pace 🏁");
+        let mut table = Table::new(&["Pos", "", "Team", "Pace", "Gap"]).align_right(&[3, 4]);
+        let fastest = pace.first().map(|(_, pace)| *pace).unwrap_or(0.0);
+
+        for (position, (team, pace)) in pace.iter().enumerate() {
+            let colour = &world.team(*team).team_color;
+            table.row(vec![
+                Cell::new(format!("P{}", position + 1)),
+                Cell::bar(colour),
+                Cell::team(world.team_name(*team), colour),
+                Cell::new(format_time(*pace)),
+                Cell::new(if position == 0 {
+                    String::new()
+                } else {
+                    format_gap(pace - fastest)
+                }),
+            ]);
+        }
+        tell!(self, "{}", table.render(self.style));
+    }
+
+    fn qualifying(&mut self, world: &World, results: &[RaceResult]) {
+        tell!(self, "\n🏁 Qualification Summary (Final Grid Order) 🏁");
+
+        // Gaps are to pole and to the car ahead on the grid, across the sections
+        let gaps = timing_gaps(results);
+        for (title, range) in qualifying_sections(results.len()) {
+            tell!(self, "\n--- {} ---", title);
+
+            let mut table =
+                Table::new(&["Pos", "", "Driver", "Name", "Team", "Time", "Gap", "Int"])
+                    .align_right(&[5, 6, 7]);
+            for (result, (gap, interval)) in
+                results.iter().zip(&gaps).take(range.end).skip(range.start)
+            {
+                let mut cells = vec![Cell::new(format!("P{}", result.finish_position))];
+                cells.extend(driver_cells(world, result.driver, result.team));
+                cells.push(Cell::new(format_time(result.finish_time_seconds)));
+                cells.push(Cell::new(gap.clone()));
+                cells.push(Cell::new(interval.clone()));
+                table.row(cells);
+            }
+            tell!(self, "{}", table.render(self.style));
+        }
+    }
+
+    fn race(&mut self, world: &World, report: &RaceReport) {
+        tell!(self, "\n🏁 Race Weekend Summary 🏁");
+        self.race_classification(world, report, points_for_position);
+    }
+
+    fn sprint(&mut self, world: &World, report: &RaceReport) {
+        tell!(self, "\n🏁 Sprint 🏁");
+        self.race_classification(world, report, sprint_points_for_position);
+    }
+
+    fn fork(&mut self, world: &World, outcome: &ForkOutcome) {
+        tell!(
+            self,
+            "\n🔀 What if {} (forked on lap {}) 🔀",
+            outcome.plan.change(),
+            outcome.plan.lap
+        );
+        tell!(
+            self,
+            "{:<5} {:<28} {:<28} Change",
+            "Pos",
+            "Original",
+            "Fork"
+        );
+
+        for row in outcome.rows(world) {
+            let moved = match row.change {
+                0 => "=".to_string(),
+                gained if gained > 0 => format!("▲{} {}", gained, row.driver),
+                lost => format!("▼{} {}", -lost, row.driver),
+            };
+            tell!(
+                self,
+                "P{:<4} {:<28} {:<28} {}",
+                row.position,
+                row.original,
+                row.fork,
+                moved
+            );
+        }
+    }
+
+    fn season_start(&mut self, rounds: usize, seed: u64) {
+        tell!(self, "🗓️ {} rounds, seed {}", rounds, seed);
+    }
+
+    fn round(&mut self, world: &World, round: usize, circuit: &Circuit, results: &[RaceResult]) {
+        if let Some(winner) = results.first() {
+            tell!(
+                self,
+                "🏁 Round {} - {}: 🏆 {} ({})",
+                round,
+                circuit.name,
+                world.driver_name(winner.driver),
+                world.team_name(winner.team)
+            );
+        }
+    }
+
+    fn standings(&mut self, world: &World, standings: &Standings) {
+        tell!(self, "\n🏆 Drivers' Championship 🏆");
+        let mut table =
+            Table::new(&["Pos", "", "Driver", "Name", "Team", "Points"]).align_right(&[5]);
+        for row in standings.driver_rows(world) {
+            let Some(driver) = row.driver_id else {
+                continue;
+            };
+            let mut cells = vec![Cell::new(format!("P{}", row.position))];
+            cells.extend(driver_cells(world, driver, row.team_id));
+            cells.push(Cell::new(row.points.to_string()));
+            table.row(cells);
+        }
+        tell!(self, "{}", table.render(self.style));
+
+        tell!(self, "\n🏆 Constructors' Championship 🏆");
+        let mut table = Table::new(&["Pos", "", "Team", "Points"]).align_right(&[3]);
+        for row in standings.team_rows(world) {
+            let colour = &world.team(row.team_id).team_color;
+            table.row(vec![
+                Cell::new(format!("P{}", row.position)),
+                Cell::bar(colour),
+                Cell::team(row.name, colour),
+                Cell::new(row.points.to_string()),
+            ]);
+        }
+        tell!(self, "{}", table.render(self.style));
+    }
+
+    fn silly_season(&mut self, world: &World, moves: &[TransferMove]) {
+        tell!(self, "\n🏁 Silly Season 🏁");
+        for transfer in moves {
+            tell!(self, "{}", transfer.info(world));
+        }
+
+        tell!(self, "\n🏁 Next Season Line-ups 🏁");
+        for (team, line_up) in line_ups(world) {
+            tell!(self, "{}: {}", team, line_up.join(" & "));
+        }
+        tell!(self, "Free agents: {}", free_agents(world).join(", "));
+    }
+
+    fn monte_carlo_start(&mut self, world: &World, grid: &[RaceResult], runs: u32, seed: u64) {
+        if let Some(pole) = grid.first() {
+            tell!(self, "⏱️ Pole: {}", world.driver_name(pole.driver));
+        }
+        tell!(self, "🎲 Racing {} times, seed {}", runs, seed);
+    }
+
+    fn note(&mut self, message: &str) {
+        tell!(self, "{}", message);
+    }
+
+    fn table(&mut self, title: &str, table: &Table) {
+        tell!(self, "\n{}", self.style.bold(title));
+        tell!(self, "{}", table.render(self.style));
+    }
+
+    fn monte_carlo(&mut self, world: &World, rows: &[MonteCarloRow], runs: u32) {
+        tell!(self, "\n🎲 Monte Carlo - {} races 🎲", runs);
+        let mut table = Table::new(&[
+            "Pos", "", "Driver", "Name", "Team", "Win", "Podium", "Points", "DNF", "Avg Pos",
+        ])
+        .align_right(&[5, 6, 7, 8, 9]);
+        for (index, row) in rows.iter().enumerate() {
+            let mut cells = vec![Cell::new(format!("P{}", index + 1))];
+            cells.extend(driver_cells(world, row.driver_id, row.team_id));
+            cells.push(Cell::new(format!("{:.1}%", row.win_share(runs))));
+            cells.push(Cell::new(format!("{:.1}%", row.podium_share(runs))));
+            cells.push(Cell::new(format!("{:.1}", row.average_points(runs))));
+            cells.push(Cell::new(format!("{:.1}%", row.dnf_share(runs))));
+            cells.push(Cell::new(format!("{:.1}", row.average_finish)));
+            table.row(cells);
+        }
+        tell!(self, "{}", table.render(self.style));
+    }
+}
+
+/// The whole weekend as one JSON document, written when the weekend is over. Names are
+/// resolved next to the IDs, like in the exported files.
+pub struct JsonReporter {
+    writer: Box<dyn Write>,
+    document: serde_json::Map<String, Value>,
+}
+
+impl JsonReporter {
+    pub fn new(writer: Box<dyn Write>) -> JsonReporter {
+        JsonReporter {
+            writer,
+            document: serde_json::Map::new(),
+        }
+    }
+
+    /// The document built so far, for callers that want the JSON itself rather than written.
+    #[cfg(any(feature = "server", test))]
+    pub fn into_document(self) -> serde_json::Map<String, Value> {
+        self.document
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn world_loaded(&mut self, _world: &World, source: &WorldSource) {
+        self.document.insert("world".to_string(), json!(source));
+    }
+
+    fn weekend_start(&mut self, world: &World, circuit: &Circuit, substitutions: &[Substitution]) {
+        let substitutions: Vec<Value> = substitutions
+            .iter()
+            .map(|substitution| {
+                json!({
+                    "team": world.team_name(substitution.team),
+                    "driver_out": world.driver_name(substitution.driver_out),
+                    "driver_in": world.driver_name(substitution.driver_in),
+                    "reason": substitution.reason.name(),
+                    "scope": substitution.scope,
+                })
+            })
+            .collect();
+
+        self.document
+            .insert("circuit".to_string(), json!(circuit.name));
+        self.document
+            .insert("laps".to_string(), json!(circuit.laps));
+        self.document
+            .insert("substitutions".to_string(), Value::Array(substitutions));
+    }
+
+    fn practice_session(&mut self, world: &World, session: &PracticeSession) {
+        let practice = self
+            .document
+            .entry("practice")
+            .or_insert_with(|| Value::Array(Vec::new()));
+
+        if let Value::Array(sessions) = practice {
+            sessions.push(json!({
+                "session": session.name,
+                "classification": classification_rows(world, &session.name, &session.results),
+                "long_runs": session.long_runs,
+            }));
+        }
+    }
+
+    fn race_pace(&mut self, world: &World, pace: &[(TeamId, f32)]) {
+        let teams: Vec<Value> = pace
+            .iter()
+            .map(|(team, pace)| {
+                json!({
+                    "team_id": team,
+                    "team": world.team_name(*team),
+                    "pace_seconds": pace,
+                })
+            })
+            .collect();
+
+        self.document
+            .insert("race_pace".to_string(), Value::Array(teams));
+    }
+
+    fn qualifying(&mut self, world: &World, results: &[RaceResult]) {
+        self.document.insert(
+            "qualifying".to_string(),
+            json!(classification_rows(world, "Qualifying", results)),
+        );
+    }
+
+    fn race(&mut self, world: &World, report: &RaceReport) {
+        self.document.insert(
+            "race".to_string(),
+            json!({
+                "classification": classification_rows(world, "Race", &report.results),
+                "team_orders": report.team_orders,
+            }),
+        );
+    }
+
+    fn sprint(&mut self, world: &World, report: &RaceReport) {
+        self.document.insert(
+            "sprint".to_string(),
+            json!({
+                "classification": classification_rows(world, "Sprint", &report.results),
+                "team_orders": report.team_orders,
+            }),
+        );
+    }
+
+    fn fork(&mut self, world: &World, outcome: &ForkOutcome) {
+        self.document.insert(
+            "fork".to_string(),
+            json!({
+                "lap": outcome.plan.lap,
+                "seed": outcome.plan.seed,
+                "change": outcome.plan.change(),
+                "classification": outcome.rows(world),
+            }),
+        );
+    }
+
+    fn season_start(&mut self, _rounds: usize, seed: u64) {
+        self.document.insert("seed".to_string(), json!(seed));
+    }
+
+    fn round(&mut self, world: &World, round: usize, circuit: &Circuit, results: &[RaceResult]) {
+        let rounds = self
+            .document
+            .entry("rounds")
+            .or_insert_with(|| Value::Array(Vec::new()));
+
+        if let Value::Array(rounds) = rounds {
+            rounds.push(json!({
+                "round": round,
+                "circuit": circuit.name,
+                "winner": results.first().map(|winner| world.driver_name(winner.driver)),
+            }));
+        }
+    }
+
+    fn standings(&mut self, world: &World, standings: &Standings) {
+        self.document.insert(
+            "standings".to_string(),
+            json!({
+                "drivers": standings.driver_rows(world),
+                "constructors": standings.team_rows(world),
+            }),
+        );
+    }
+
+    fn silly_season(&mut self, world: &World, moves: &[TransferMove]) {
+        let moves: Vec<Value> = moves
+            .iter()
+            .map(|transfer| {
+                let mut value = json!(transfer);
+                value["description"] = json!(transfer.description(world));
+                value
+            })
+            .collect();
+        let line_ups: Vec<Value> = line_ups(world)
+            .into_iter()
+            .map(|(team, drivers)| json!({ "team": team, "drivers": drivers }))
+            .collect();
+
+        self.document.insert(
+            "silly_season".to_string(),
+            json!({
+                "moves": moves,
+                "line_ups": line_ups,
+                "free_agents": free_agents(world),
+            }),
+        );
+    }
+
+    fn monte_carlo_start(&mut self, world: &World, grid: &[RaceResult], _runs: u32, seed: u64) {
+        self.document.insert("seed".to_string(), json!(seed));
+        self.document.insert(
+            "qualifying".to_string(),
+            json!(classification_rows(world, "Qualifying", grid)),
+        );
+    }
+
+    fn monte_carlo(&mut self, _world: &World, rows: &[MonteCarloRow], runs: u32) {
+        self.document.insert(
+            "monte_carlo".to_string(),
+            json!({
+                "runs": runs,
+                "drivers": rows,
+            }),
+        );
+    }
+
+    fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer_pretty(&mut self.writer, &self.document)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Every classification of the weekend in one CSV table, told apart by the session column.
+/// A fork, standings and Monte Carlo results get a table of their own.
+pub struct CsvReporter {
+    writer: Box<dyn Write>,
+    rows: Vec<ClassificationRow>,
+    fork: Vec<ForkRow>,
+    standings: Vec<StandingRow>,
+    monte_carlo: Vec<MonteCarloRow>,
+}
+
+impl CsvReporter {
+    pub fn new(writer: Box<dyn Write>) -> CsvReporter {
+        CsvReporter {
+            writer,
+            rows: Vec::new(),
+            fork: Vec::new(),
+            standings: Vec::new(),
+            monte_carlo: Vec::new(),
+        }
+    }
+
+    /// Writes `rows` as a table with its own header row, if there are any.
+    fn write_table<T: Serialize>(&mut self, rows: &[T]) -> Result<(), Box<dyn Error>> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        let mut writer = csv::Writer::from_writer(&mut self.writer);
+        for row in rows {
+            writer.serialize(row)?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+impl Reporter for CsvReporter {
+    // Only the classifications fit in the table
+    fn weekend_start(
+        &mut self,
+        _world: &World,
+        _circuit: &Circuit,
+        _substitutions: &[Substitution],
+    ) {
+    }
+
+    fn practice_session(&mut self, world: &World, session: &PracticeSession) {
+        self.rows
+            .extend(classification_rows(world, &session.name, &session.results));
+    }
+
+    fn qualifying(&mut self, world: &World, results: &[RaceResult]) {
+        self.rows
+            .extend(classification_rows(world, "Qualifying", results));
+    }
+
+    fn race(&mut self, world: &World, report: &RaceReport) {
+        self.rows
+            .extend(classification_rows(world, "Race", &report.results));
+    }
+
+    fn sprint(&mut self, world: &World, report: &RaceReport) {
+        self.rows
+            .extend(classification_rows(world, "Sprint", &report.results));
+    }
+
+    fn fork(&mut self, world: &World, outcome: &ForkOutcome) {
+        self.fork.extend(outcome.rows(world));
+    }
+
+    fn standings(&mut self, world: &World, standings: &Standings) {
+        self.standings.extend(standings.driver_rows(world));
+        self.standings.extend(standings.team_rows(world));
+    }
+
+    fn monte_carlo(&mut self, _world: &World, rows: &[MonteCarloRow], _runs: u32) {
+        self.monte_carlo.extend_from_slice(rows);
+    }
+
+    fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        let rows = std::mem::take(&mut self.rows);
+        self.write_table(&rows)?;
+        let fork = std::mem::take(&mut self.fork);
+        self.write_table(&fork)?;
+        let standings = std::mem::take(&mut self.standings);
+        self.write_table(&standings)?;
+        let monte_carlo = std::mem::take(&mut self.monte_carlo);
+        self.write_table(&monte_carlo)?;
+
+        Ok(())
+    }
+}
+
+/// Markdown tables for every session, ready to paste into an issue or a wiki page.
+pub struct MarkdownReporter {
+    writer: Box<dyn Write>,
+    document: String,
+}
+
+impl MarkdownReporter {
+    pub fn new(writer: Box<dyn Write>) -> MarkdownReporter {
+        MarkdownReporter {
+            writer,
+            document: String::new(),
+        }
+    }
+
+    fn table(&mut self, headers: &[&str], rows: Vec<Vec<String>>) {
+        let separators: Vec<&str> = headers.iter().map(|_| "---").collect();
+        self.document
+            .push_str(&format!("| {} |\n", headers.join(" | ")));
+        self.document
+            .push_str(&format!("| {} |\n", separators.join(" | ")));
+        for row in rows {
+            let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+            self.document
+                .push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        self.document.push('\n');
+    }
+
+    /// Rows of a classification by lap time with the gap and interval.
+    fn lap_time_rows(world: &World, results: &[RaceResult]) -> Vec<Vec<String>> {
+        results
+            .iter()
+            .zip(timing_gaps(results))
+            .map(|(result, (gap, interval))| {
+                vec![
+                    format!("P{}", result.finish_position),
+                    world.driver(result.driver).name_acronym.clone(),
+                    world.driver_name(result.driver).to_string(),
+                    world.team_name(result.team).to_string(),
+                    format_time(result.finish_time_seconds),
+                    gap,
+                    interval,
+                ]
+            })
+            .collect()
+    }
+
+    /// A race or sprint classification with the points `points` gives, and the team orders.
+    fn race_section(
+        &mut self,
+        title: &str,
+        world: &World,
+        report: &RaceReport,
+        points: fn(u8) -> u32,
+    ) {
+        self.document.push_str(&format!("## {}\n\n", title));
+        let rows = report
+            .results
+            .iter()
+            .zip(race_columns(report, points))
+            .map(|(result, (time, interval, stops, points))| {
+                vec![
+                    race_position(result),
+                    world.driver(result.driver).name_acronym.clone(),
+                    world.driver_name(result.driver).to_string(),
+                    world.team_name(result.team).to_string(),
+                    time,
+                    interval,
+                    stops.to_string(),
+                    points.to_string(),
+                ]
+            })
+            .collect();
+        self.table(
+            &[
+                "Pos", "Driver", "Name", "Team", "Time", "Int", "Stops", "Points",
+            ],
+            rows,
+        );
+
+        if !report.team_orders.is_empty() {
+            self.document.push_str("### Team Orders\n\n");
+            for order in &report.team_orders {
+                self.document
+                    .push_str(&format!("- {}\n", order.info(world)));
+            }
+            self.document.push('\n');
+        }
+    }
+}
+
+const LAP_TIME_HEADERS: [&str; 7] = ["Pos", "Driver", "Name", "Team", "Time", "Gap", "Int"];
+
+impl Reporter for MarkdownReporter {
+    fn weekend_start(&mut self, world: &World, circuit: &Circuit, substitutions: &[Substitution]) {
+        self.document.push_str(&format!(
+            "# {} ({})\n\n{} laps\n\n",
+            circuit.name, circuit.country, circuit.laps
+        ));
+        for substitution in substitutions {
+            self.document
+                .push_str(&format!("- {}\n", substitution.info(world)));
+        }
+        if !substitutions.is_empty() {
+            self.document.push('\n');
+        }
+    }
+
+    fn practice_session(&mut self, world: &World, session: &PracticeSession) {
+        self.document.push_str(&format!("## {}\n\n", session.name));
+        let rows = MarkdownReporter::lap_time_rows(world, &session.results);
+        self.table(&LAP_TIME_HEADERS, rows);
+    }
+
+    fn race_pace(&mut self, world: &World, pace: &[(TeamId, f32)]) {
+        self.document.push_str("## Predicted Race Pace\n\n");
+        let rows = pace
+            .iter()
+            .enumerate()
+            .map(|(position, (team, pace))| {
+                vec![
+                    format!("P{}", position + 1),
+                    world.team_name(*team).to_string(),
+                    format_time(*pace),
+                ]
+            })
+            .collect();
+        self.table(&["Pos", "Team", "Pace"], rows);
+    }
+
+    fn qualifying(&mut self, world: &World, results: &[RaceResult]) {
+        self.document.push_str("## Qualifying\n\n");
+        let rows = MarkdownReporter::lap_time_rows(world, results);
+
+        for (title, range) in qualifying_sections(results.len()) {
+            self.document.push_str(&format!("### {}\n\n", title));
+            let section = rows.get(range).unwrap_or_default().to_vec();
+            self.table(&LAP_TIME_HEADERS, section);
+        }
+    }
+
+    fn race(&mut self, world: &World, report: &RaceReport) {
+        self.race_section("Race", world, report, points_for_position);
+    }
+
+    fn sprint(&mut self, world: &World, report: &RaceReport) {
+        self.race_section("Sprint", world, report, sprint_points_for_position);
+    }
+
+    fn fork(&mut self, world: &World, outcome: &ForkOutcome) {
+        self.document.push_str(&format!(
+            "## What if {} (forked on lap {})\n\n",
+            outcome.plan.change(),
+            outcome.plan.lap
+        ));
+        let rows = outcome
+            .rows(world)
+            .into_iter()
+            .map(|row| {
+                vec![
+                    format!("P{}", row.position),
+                    row.original,
+                    row.fork,
+                    match row.change {
+                        0 => "=".to_string(),
+                        change => format!("{:+} {}", change, row.driver),
+                    },
+                ]
+            })
+            .collect();
+        self.table(&["Pos", "Original", "Fork", "Change"], rows);
+    }
+
+    fn standings(&mut self, world: &World, standings: &Standings) {
+        self.document.push_str("## Drivers' Championship\n\n");
+        let rows = standings
+            .driver_rows(world)
+            .into_iter()
+            .map(|row| {
+                vec![
+                    format!("P{}", row.position),
+                    row.name,
+                    row.team,
+                    row.points.to_string(),
+                ]
+            })
+            .collect();
+        self.table(&["Pos", "Driver", "Team", "Points"], rows);
+
+        self.document.push_str("## Constructors' Championship\n\n");
+        let rows = standings
+            .team_rows(world)
+            .into_iter()
+            .map(|row| {
+                vec![
+                    format!("P{}", row.position),
+                    row.name,
+                    row.points.to_string(),
+                ]
+            })
+            .collect();
+        self.table(&["Pos", "Team", "Points"], rows);
+    }
+
+    fn silly_season(&mut self, world: &World, moves: &[TransferMove]) {
+        self.document.push_str("## Silly Season\n\n");
+        for transfer in moves {
+            self.document
+                .push_str(&format!("- {}\n", transfer.description(world)));
+        }
+        self.document.push('\n');
+
+        self.document.push_str("## Next Season Line-ups\n\n");
+        let rows = line_ups(world)
+            .into_iter()
+            .map(|(team, drivers)| vec![team.to_string(), drivers.join(" & ")])
+            .collect();
+        self.table(&["Team", "Drivers"], rows);
+    }
+
+    fn monte_carlo(&mut self, _world: &World, rows: &[MonteCarloRow], runs: u32) {
+        self.document
+            .push_str(&format!("## Monte Carlo ({} races)\n\n", runs));
+        let rows = rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                vec![
+                    format!("P{}", index + 1),
+                    row.acronym.clone(),
+                    row.driver.clone(),
+                    row.team.clone(),
+                    format!("{:.1}%", row.win_share(runs)),
+                    format!("{:.1}%", row.podium_share(runs)),
+                    format!("{:.1}", row.average_points(runs)),
+                    format!("{:.1}%", row.dnf_share(runs)),
+                    format!("{:.1}", row.average_finish),
+                ]
+            })
+            .collect();
+        self.table(
+            &[
+                "Pos", "Driver", "Name", "Team", "Win", "Podium", "Points", "DNF", "Avg Pos",
+            ],
+            rows,
+        );
+    }
+
+    fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        self.writer.write_all(self.document.as_bytes())?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Every team's race drivers by name, in team order.
+fn line_ups(world: &World) -> Vec<(&str, Vec<&str>)> {
+    world
+        .teams()
+        .iter()
+        .map(|team| {
+            let drivers = team
+                .drivers
+                .iter()
+                .map(|driver| world.driver_name(*driver))
+                .collect();
+            (team.name.as_str(), drivers)
+        })
+        .collect()
+}
+
+fn free_agents(world: &World) -> Vec<&str> {
+    world
+        .free_agents()
+        .iter()
+        .map(|driver| world.driver_name(*driver))
+        .collect()
+}
+
+/// The qualifying knockouts and the grid positions that went out in each.
+fn qualifying_sections(drivers: usize) -> [(&'static str, Range<usize>); 3] {
+    [
+        ("Q3 Results", 0..10.min(drivers)),
+        ("Eliminated in Q2", 10.min(drivers)..15.min(drivers)),
+        ("Eliminated in Q1", 15.min(drivers)..drivers),
+    ]
+}
+
+/// Gap to the first car and interval to the car ahead for every classified result, as shown
+/// on the timing screens. Retired cars have neither.
+fn timing_gaps(results: &[RaceResult]) -> Vec<(String, String)> {
+    let leader_time = results.first().map(|result| result.finish_time_seconds);
+    let mut previous_time = leader_time;
+
+    results
+        .iter()
+        .map(|result| match (leader_time, previous_time) {
+            (Some(leader), Some(previous)) if !result.dnf && result.finish_position > 1 => {
+                previous_time = Some(result.finish_time_seconds);
+                (
+                    format_gap(result.finish_time_seconds - leader),
+                    format_gap(result.finish_time_seconds - previous),
+                )
+            }
+            _ => (String::new(), String::new()),
+        })
+        .collect()
+}
+
+fn race_position(result: &RaceResult) -> String {
+    if result.dnf {
+        "DNF".to_string()
+    } else {
+        format!("P{}", result.finish_position)
+    }
+}
+
+/// Race time or gap, interval, pit stops and `points` for every line of the classification.
+fn race_columns(report: &RaceReport, points: fn(u8) -> u32) -> Vec<(String, String, usize, u32)> {
+    report
+        .results
+        .iter()
+        .zip(timing_gaps(&report.results))
+        .map(|(result, (gap, interval))| {
+            let laps = report.laps_of(result.driver);
+            let time = if result.dnf {
+                format!("DNF (lap {})", laps.len() + 1)
+            } else if result.finish_position == 1 {
+                format_time(result.finish_time_seconds)
+            } else {
+                gap
+            };
+            let points = if result.dnf {
+                0
+            } else {
+                points(result.finish_position)
+            };
+
+            (
+                time,
+                interval,
+                laps.iter().filter(|lap| lap.pitted).count(),
+                points,
+            )
+        })
+        .collect()
+}
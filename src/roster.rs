@@ -0,0 +1,630 @@
+use crate::error::{RosterError, RosterProblem};
+use crate::market::FREE_AGENT;
+use crate::models::{Car, Driver, DriverId, Team, TeamId, TeamOrderPolicy};
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Folder searched for named rosters such as `2025`.
+pub const ROSTER_DIR: &str = "data/rosters";
+
+/// The roster that ships with the simulator and is used when no other one is asked for.
+pub const DEFAULT_ROSTER: &str = "2024";
+
+// The default roster is compiled in so the simulator works from any directory
+const BUILT_IN_2024: &str = include_str!("../data/rosters/2024.json");
+
+/// A team's car as stored in a roster file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RosterCar {
+    pub team_name: String,
+    #[serde(flatten)]
+    pub car: Car,
+}
+
+/// A driver as stored in a roster file, with the team they belong to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RosterDriver {
+    pub team_name: String,
+    #[serde(flatten)]
+    pub driver: Driver,
+}
+
+/// A team's metadata as stored in a roster file. Drivers and cars refer to it by name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RosterTeam {
+    pub name: String,
+    pub team_color: String,
+    pub nationality: String,
+    pub driver_budget: f32, // in millions of USD per season
+    pub order_policy: RosterOrderPolicy,
+}
+
+/// A team's order policy as stored in a roster file, naming the number one driver.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RosterOrderPolicy {
+    FreeRacing,
+    HoldPosition,
+    SwapForStrategy,
+    NumberOneDriver(String),
+}
+
+impl RosterOrderPolicy {
+    /// The policy with the number one driver looked up among `team`'s race drivers.
+    fn resolve(&self, world: &World, team: TeamId) -> Result<TeamOrderPolicy, RosterError> {
+        Ok(match self {
+            RosterOrderPolicy::FreeRacing => TeamOrderPolicy::FreeRacing,
+            RosterOrderPolicy::HoldPosition => TeamOrderPolicy::HoldPosition,
+            RosterOrderPolicy::SwapForStrategy => TeamOrderPolicy::SwapForStrategy,
+            RosterOrderPolicy::NumberOneDriver(name) => {
+                let driver = world
+                    .team(team)
+                    .drivers
+                    .iter()
+                    .copied()
+                    .find(|driver| world.driver_name(*driver) == name)
+                    .ok_or_else(|| {
+                        RosterError::Invalid(vec![RosterProblem::NumberOneNotInTeam {
+                            team: world.team_name(team).to_string(),
+                            driver: name.clone(),
+                        }])
+                    })?;
+                TeamOrderPolicy::NumberOneDriver(driver)
+            }
+        })
+    }
+}
+
+/// Everyone and everything on the grid for one season, loaded from a JSON data file.
+/// Files refer to teams by name so they stay easy to edit, the `World` built from a roster
+/// refers to everything by ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Roster {
+    pub season: u16,
+    pub teams: Vec<RosterTeam>,
+    pub cars: Vec<RosterCar>,
+    pub drivers: Vec<RosterDriver>,
+    pub reserve_drivers: Vec<RosterDriver>,
+    #[serde(default)]
+    pub rookies: Vec<RosterDriver>,
+}
+
+impl Roster {
+    /// Loads a roster by name (`2024`, `2025`, ...) from the roster folder, or from a path to
+    /// any JSON roster file. The default roster is always available, even without the folder.
+    /// The roster is validated before it is returned.
+    pub fn load(name_or_path: &str) -> Result<Roster, RosterError> {
+        let named_path = Path::new(ROSTER_DIR).join(format!("{}.json", name_or_path));
+
+        let contents = if named_path.exists() {
+            fs::read_to_string(named_path)?
+        } else if name_or_path == DEFAULT_ROSTER {
+            BUILT_IN_2024.to_string()
+        } else {
+            fs::read_to_string(name_or_path)?
+        };
+
+        let roster: Roster = serde_json::from_str(&contents)?;
+        let problems = roster.validate();
+        if !problems.is_empty() {
+            return Err(RosterError::Invalid(problems));
+        }
+
+        Ok(roster)
+    }
+
+    /// Checks the roster for every problem that would break a simulation or make no sense on
+    /// the grid. Returns all problems found, an empty list means the roster is valid.
+    pub fn validate(&self) -> Vec<RosterProblem> {
+        let mut problems = Vec::new();
+        let known_team = |name: &str| self.teams.iter().any(|team| team.name == name);
+
+        for team in &self.teams {
+            let is_hex = team.team_color.len() == 6
+                && team.team_color.chars().all(|c| c.is_ascii_hexdigit());
+            if !is_hex {
+                problems.push(RosterProblem::InvalidColour {
+                    team: team.name.clone(),
+                    colour: team.team_color.clone(),
+                });
+            }
+
+            let count = self
+                .drivers
+                .iter()
+                .filter(|entry| entry.team_name == team.name)
+                .count();
+            if count != 2 {
+                problems.push(RosterProblem::WrongDriverCount {
+                    team: team.name.clone(),
+                    count,
+                });
+            }
+
+            if let RosterOrderPolicy::NumberOneDriver(name) = &team.order_policy {
+                let races_for_team = self
+                    .drivers
+                    .iter()
+                    .any(|entry| entry.team_name == team.name && entry.driver.full_name == *name);
+                if !races_for_team {
+                    problems.push(RosterProblem::NumberOneNotInTeam {
+                        team: team.name.clone(),
+                        driver: name.clone(),
+                    });
+                }
+            }
+
+            if !self.cars.iter().any(|car| car.team_name == team.name) {
+                problems.push(RosterProblem::MissingCar {
+                    team: team.name.clone(),
+                });
+            }
+        }
+
+        for entry in &self.cars {
+            if !known_team(&entry.team_name) {
+                problems.push(RosterProblem::CarWithoutTeam {
+                    car: entry.car.name.clone(),
+                    team: entry.team_name.clone(),
+                });
+            }
+
+            let car = &entry.car;
+            for (attribute, value) in [
+                ("engine power", car.engine_power),
+                ("tyre management", car.tyre_management),
+                ("reliability", car.reliability),
+                ("aerodynamics", car.aerodynamics),
+            ] {
+                if value > 100 {
+                    problems.push(RosterProblem::AttributeOutOfRange {
+                        owner: car.name.clone(),
+                        attribute,
+                        value,
+                    });
+                }
+            }
+        }
+
+        // Reserves and rookies can end up racing, so numbers and acronyms must be unique
+        // across everyone in the roster
+        let everyone: Vec<&RosterDriver> = self
+            .drivers
+            .iter()
+            .chain(&self.reserve_drivers)
+            .chain(&self.rookies)
+            .collect();
+
+        for (index, entry) in everyone.iter().enumerate() {
+            let driver = &entry.driver;
+            if !known_team(&entry.team_name) && entry.team_name != FREE_AGENT {
+                problems.push(RosterProblem::UnknownTeam {
+                    driver: driver.full_name.clone(),
+                    team: entry.team_name.clone(),
+                });
+            }
+
+            for (attribute, value) in [
+                ("skill", driver.skill_level),
+                ("experience", driver.experience_level),
+                ("aggression", driver.aggression_level),
+                ("consistency", driver.consistency_level),
+            ] {
+                if value > 100 {
+                    problems.push(RosterProblem::AttributeOutOfRange {
+                        owner: driver.full_name.clone(),
+                        attribute,
+                        value,
+                    });
+                }
+            }
+
+            for other in everyone[..index].iter().map(|entry| &entry.driver) {
+                if other.driver_number == driver.driver_number {
+                    problems.push(RosterProblem::DuplicateNumber {
+                        number: driver.driver_number,
+                        first: other.full_name.clone(),
+                        second: driver.full_name.clone(),
+                    });
+                }
+                if other.name_acronym == driver.name_acronym {
+                    problems.push(RosterProblem::DuplicateAcronym {
+                        acronym: driver.name_acronym.clone(),
+                        first: other.full_name.clone(),
+                        second: driver.full_name.clone(),
+                    });
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// The roster a world would be saved as: every team with its car, race drivers and
+    /// reserves, and the free agents as rookies. Retired drivers are left out. Used to run the
+    /// roster checks on a world that was loaded from elsewhere, like a career save.
+    pub fn from_world(season: u16, world: &World) -> Result<Roster, RosterError> {
+        let mut roster = Roster {
+            season,
+            teams: Vec::new(),
+            cars: Vec::new(),
+            drivers: Vec::new(),
+            reserve_drivers: Vec::new(),
+            rookies: Vec::new(),
+        };
+        let mut problems = Vec::new();
+        let driver_entry = |id: DriverId, team_name: &str| {
+            world
+                .all_drivers()
+                .get(id.0 as usize)
+                .map(|driver| RosterDriver {
+                    team_name: team_name.to_string(),
+                    driver: driver.clone(),
+                })
+        };
+
+        for team in world.teams() {
+            let order_policy = match team.order_policy {
+                TeamOrderPolicy::FreeRacing => RosterOrderPolicy::FreeRacing,
+                TeamOrderPolicy::HoldPosition => RosterOrderPolicy::HoldPosition,
+                TeamOrderPolicy::SwapForStrategy => RosterOrderPolicy::SwapForStrategy,
+                TeamOrderPolicy::NumberOneDriver(id) => match driver_entry(id, &team.name) {
+                    Some(entry) => RosterOrderPolicy::NumberOneDriver(entry.driver.full_name),
+                    None => {
+                        problems.push(RosterProblem::UnknownDriver {
+                            team: team.name.clone(),
+                            id: id.0,
+                        });
+                        RosterOrderPolicy::FreeRacing
+                    }
+                },
+            };
+            roster.teams.push(RosterTeam {
+                name: team.name.clone(),
+                team_color: team.team_color.clone(),
+                nationality: team.nationality.clone(),
+                driver_budget: team.driver_budget,
+                order_policy,
+            });
+            if let Some(car) = world.cars().get(team.car.0 as usize) {
+                roster.cars.push(RosterCar {
+                    team_name: team.name.clone(),
+                    car: car.clone(),
+                });
+            }
+
+            for (ids, entries) in [
+                (&team.drivers, &mut roster.drivers),
+                (&team.reserve_drivers, &mut roster.reserve_drivers),
+            ] {
+                for id in ids {
+                    match driver_entry(*id, &team.name) {
+                        Some(entry) => entries.push(entry),
+                        None => problems.push(RosterProblem::UnknownDriver {
+                            team: team.name.clone(),
+                            id: id.0,
+                        }),
+                    }
+                }
+            }
+        }
+        for id in world.free_agents() {
+            match driver_entry(*id, FREE_AGENT) {
+                Some(entry) => roster.rookies.push(entry),
+                None => problems.push(RosterProblem::UnknownDriver {
+                    team: FREE_AGENT.to_string(),
+                    id: id.0,
+                }),
+            }
+        }
+
+        problems.extend(roster.validate());
+        if !problems.is_empty() {
+            return Err(RosterError::Invalid(problems));
+        }
+
+        Ok(roster)
+    }
+
+    /// Names of the rosters found in the roster folder, sorted.
+    pub fn available() -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(ROSTER_DIR)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter_map(|entry| {
+                        let path = entry.path();
+                        if path.extension()? != "json" {
+                            return None;
+                        }
+                        Some(path.file_stem()?.to_string_lossy().to_string())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if !names.iter().any(|name| name == DEFAULT_ROSTER) {
+            names.push(DEFAULT_ROSTER.to_string());
+        }
+        names.sort();
+        names
+    }
+
+    /// Builds the world for this roster: every team with its car, race drivers and reserves,
+    /// and the rookies as free agents.
+    pub fn to_world(&self) -> Result<World, RosterError> {
+        let mut world = World::new();
+
+        for info in &self.teams {
+            let car = self
+                .cars
+                .iter()
+                .find(|entry| entry.team_name == info.name)
+                .map(|entry| entry.car.clone())
+                .ok_or_else(|| {
+                    RosterError::Invalid(vec![RosterProblem::MissingCar {
+                        team: info.name.clone(),
+                    }])
+                })?;
+            let car = world.add_car(car);
+
+            world.add_team(Team::new(
+                info.name.clone(),
+                info.team_color.clone(),
+                info.nationality.clone(),
+                car,
+                info.driver_budget,
+                TeamOrderPolicy::FreeRacing, // until the number one driver has been signed
+            ));
+        }
+
+        let team_of = |world: &World, entry: &RosterDriver| {
+            world.find_team(&entry.team_name).ok_or_else(|| {
+                RosterError::Invalid(vec![RosterProblem::UnknownTeam {
+                    driver: entry.driver.full_name.clone(),
+                    team: entry.team_name.clone(),
+                }])
+            })
+        };
+
+        for entry in &self.drivers {
+            let team = team_of(&world, entry)?;
+            let driver = world.add_driver(entry.driver.clone());
+            world.sign_race_driver(driver, team);
+        }
+        for entry in &self.reserve_drivers {
+            let team = team_of(&world, entry)?;
+            let driver = world.add_driver(entry.driver.clone());
+            world.sign_reserve(driver, team);
+        }
+        for entry in &self.rookies {
+            world.add_driver(entry.driver.clone());
+        }
+        for (info, team) in self.teams.iter().zip(world.team_ids()) {
+            world.team_mut(team).order_policy = info.order_policy.resolve(&world, team)?;
+        }
+
+        Ok(world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One team, two drivers and a car, valid as it is.
+    const SMALL_ROSTER: &str = r#"{
+        "season": 2024,
+        "teams": [{
+            "name": "Test Racing",
+            "team_color": "FF8700",
+            "nationality": "British",
+            "driver_budget": 40.0,
+            "order_policy": { "NumberOneDriver": "Ada Fast" }
+        }],
+        "cars": [{
+            "team_name": "Test Racing",
+            "name": "TR1",
+            "engine_power": 90,
+            "tyre_management": 85,
+            "reliability": 95,
+            "aerodynamics": 88
+        }],
+        "drivers": [
+            {
+                "team_name": "Test Racing",
+                "first_name": "Ada",
+                "last_name": "Fast",
+                "full_name": "Ada Fast",
+                "name_acronym": "FAS",
+                "age": 25,
+                "country": "GB",
+                "driver_number": 4,
+                "skill_level": 90,
+                "experience_level": 70,
+                "aggression_level": 60,
+                "consistency_level": 80
+            },
+            {
+                "team_name": "Test Racing",
+                "first_name": "Bo",
+                "last_name": "Steady",
+                "full_name": "Bo Steady",
+                "name_acronym": "STE",
+                "age": 31,
+                "country": "SE",
+                "driver_number": 81,
+                "skill_level": 85,
+                "experience_level": 80,
+                "aggression_level": 40,
+                "consistency_level": 90
+            }
+        ],
+        "reserve_drivers": []
+    }"#;
+
+    fn small_roster() -> Roster {
+        serde_json::from_str(SMALL_ROSTER).unwrap()
+    }
+
+    #[test]
+    fn small_roster_is_valid() {
+        assert_eq!(small_roster().validate(), Vec::new());
+    }
+
+    #[test]
+    fn driver_of_an_unknown_team() {
+        let mut roster = small_roster();
+        let mut reserve = roster.drivers[1].clone();
+        reserve.team_name = "Brawn GP".to_string();
+        reserve.driver.full_name = "Cy Spare".to_string();
+        reserve.driver.name_acronym = "SPA".to_string();
+        reserve.driver.driver_number = 22;
+        roster.reserve_drivers.push(reserve);
+
+        assert_eq!(
+            roster.validate(),
+            vec![RosterProblem::UnknownTeam {
+                driver: "Cy Spare".to_string(),
+                team: "Brawn GP".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn team_with_one_driver() {
+        let mut roster = small_roster();
+        roster.drivers.pop();
+
+        assert_eq!(
+            roster.validate(),
+            vec![RosterProblem::WrongDriverCount {
+                team: "Test Racing".to_string(),
+                count: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn drivers_sharing_a_number() {
+        let mut roster = small_roster();
+        roster.drivers[1].driver.driver_number = 4;
+
+        assert_eq!(
+            roster.validate(),
+            vec![RosterProblem::DuplicateNumber {
+                number: 4,
+                first: "Ada Fast".to_string(),
+                second: "Bo Steady".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn drivers_sharing_an_acronym() {
+        let mut roster = small_roster();
+        roster.drivers[1].driver.name_acronym = "FAS".to_string();
+
+        assert_eq!(
+            roster.validate(),
+            vec![RosterProblem::DuplicateAcronym {
+                acronym: "FAS".to_string(),
+                first: "Ada Fast".to_string(),
+                second: "Bo Steady".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn attribute_above_100() {
+        let mut roster = small_roster();
+        roster.cars[0].car.reliability = 150;
+
+        assert_eq!(
+            roster.validate(),
+            vec![RosterProblem::AttributeOutOfRange {
+                owner: "TR1".to_string(),
+                attribute: "reliability",
+                value: 150,
+            }]
+        );
+    }
+
+    #[test]
+    fn colour_that_isnt_hex() {
+        let mut roster = small_roster();
+        roster.teams[0].team_color = "orange".to_string();
+
+        assert_eq!(
+            roster.validate(),
+            vec![RosterProblem::InvalidColour {
+                team: "Test Racing".to_string(),
+                colour: "orange".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn car_of_an_unknown_team() {
+        let mut roster = small_roster();
+        let mut car = roster.cars[0].clone();
+        car.team_name = "Brawn GP".to_string();
+        car.car.name = "BGP 001".to_string();
+        roster.cars.push(car);
+
+        assert_eq!(
+            roster.validate(),
+            vec![RosterProblem::CarWithoutTeam {
+                car: "BGP 001".to_string(),
+                team: "Brawn GP".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn team_without_a_car() {
+        let mut roster = small_roster();
+        roster.cars.clear();
+
+        assert_eq!(
+            roster.validate(),
+            vec![RosterProblem::MissingCar {
+                team: "Test Racing".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn number_one_from_another_team() {
+        let mut roster = small_roster();
+        roster.teams[0].order_policy =
+            RosterOrderPolicy::NumberOneDriver("Max Verstappen".to_string());
+
+        assert_eq!(
+            roster.validate(),
+            vec![RosterProblem::NumberOneNotInTeam {
+                team: "Test Racing".to_string(),
+                driver: "Max Verstappen".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn world_referring_to_a_missing_driver() {
+        let mut world = small_roster().to_world().unwrap();
+        let team = world.team_ids()[0];
+        world.team_mut(team).reserve_drivers.push(DriverId(99));
+
+        match Roster::from_world(2024, &world) {
+            Err(RosterError::Invalid(problems)) => assert_eq!(
+                problems,
+                vec![RosterProblem::UnknownDriver {
+                    team: "Test Racing".to_string(),
+                    id: 99,
+                }]
+            ),
+            other => panic!("expected an unknown driver, got {:?}", other.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::career::add_points;
+use crate::error::SimulationError;
+use crate::events::EventStream;
+use crate::models::{Circuit, DriverId, RaceResult, TeamId};
+use crate::reporter::Reporter;
+use crate::session::{Qualifying, Race, Session, SessionInput, session_rng};
+use crate::utils::*;
+use crate::world::World;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Championship points after a run of races, highest first.
+#[derive(Debug, Clone, Default)]
+pub struct Standings {
+    pub drivers: Vec<(DriverId, u32)>,
+    pub teams: Vec<(TeamId, u32)>,
+}
+
+/// One line of the drivers' or constructors' championship, with names resolved like the
+/// exported classifications.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandingRow {
+    pub championship: String, // "Drivers" or "Constructors"
+    pub position: usize,
+    pub driver_id: Option<DriverId>,
+    pub name: String,
+    pub team_id: TeamId,
+    pub team: String,
+    pub points: u32,
+}
+
+impl Standings {
+    /// The points one race scores, as if it were the only round.
+    pub fn from_race(results: &[RaceResult]) -> Standings {
+        let mut standings = Standings::default();
+        standings.add_race(results);
+        standings.sort();
+
+        standings
+    }
+
+    fn add_race(&mut self, results: &[RaceResult]) {
+        for (result, (driver, points)) in results.iter().zip(championship_points(results)) {
+            add_points(&mut self.drivers, driver, points);
+            add_points(&mut self.teams, result.team, points);
+        }
+    }
+
+    fn sort(&mut self) {
+        self.drivers
+            .sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+        self.teams
+            .sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+    }
+
+    pub fn driver_rows(&self, world: &World) -> Vec<StandingRow> {
+        self.drivers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (driver, points))| {
+                let team = world.team_of(*driver).ok()?;
+
+                Some(StandingRow {
+                    championship: "Drivers".to_string(),
+                    position: index + 1,
+                    driver_id: Some(*driver),
+                    name: world.driver_name(*driver).to_string(),
+                    team_id: team,
+                    team: world.team_name(team).to_string(),
+                    points: *points,
+                })
+            })
+            .collect()
+    }
+
+    pub fn team_rows(&self, world: &World) -> Vec<StandingRow> {
+        self.teams
+            .iter()
+            .enumerate()
+            .map(|(index, (team, points))| StandingRow {
+                championship: "Constructors".to_string(),
+                position: index + 1,
+                driver_id: None,
+                name: world.team_name(*team).to_string(),
+                team_id: *team,
+                team: world.team_name(*team).to_string(),
+                points: *points,
+            })
+            .collect()
+    }
+}
+
+/// Races every round of `calendar` with the current line-ups, from setups as three practice
+/// sessions would leave them. Each round's seed comes from `seed` and every session draws on
+/// its own generator from it, so a season can be repeated. Every round's result goes to
+/// `reporter`.
+pub fn run_season(
+    world: &World,
+    calendar: &[Circuit],
+    seed: u64,
+    reporter: &mut dyn Reporter,
+) -> Result<Standings, SimulationError> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut standings = Standings {
+        drivers: world
+            .race_drivers()
+            .into_iter()
+            .map(|driver| (driver, 0))
+            .collect(),
+        teams: world.team_ids().into_iter().map(|team| (team, 0)).collect(),
+    };
+
+    for (round, circuit) in calendar.iter().enumerate() {
+        let round_seed: u64 = rng.random();
+        let drivers = world.race_drivers();
+        let mut setups = practice_setups(
+            world,
+            &drivers,
+            circuit,
+            3,
+            &mut session_rng(round_seed, "Practice"),
+        );
+        let qualifying = Qualifying::new("Qualifying").run(SessionInput {
+            world,
+            entrants: &drivers,
+            circuit,
+            setups: &mut setups,
+            grid: &[],
+            rng: session_rng(round_seed, "Qualifying"),
+            events: &mut EventStream::disabled(),
+        })?;
+        let race = Race::after("Qualifying").run(SessionInput {
+            world,
+            entrants: &drivers,
+            circuit,
+            setups: &mut setups,
+            grid: &qualifying.classification,
+            rng: session_rng(round_seed, "Race"),
+            events: &mut EventStream::disabled(),
+        })?;
+
+        standings.add_race(&race.classification);
+
+        reporter.round(world, round + 1, circuit, &race.classification);
+    }
+
+    standings.sort();
+
+    Ok(standings)
+}
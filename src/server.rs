@@ -0,0 +1,433 @@
+use crate::error::RosterError;
+use crate::models::{PlannedSubstitution, Weather};
+use crate::reporter::{JsonReporter, Reporter};
+use crate::roster::{DEFAULT_ROSTER, Roster};
+use crate::season::Standings;
+use crate::simulator::{SimulationConfig, Simulator};
+use crate::utils::*;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{self, Read};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Largest request body read, plenty for a roster file.
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+
+/// Runs kept for fetching again. Once there are more the oldest are forgotten.
+const MAX_RUNS: usize = 100;
+
+/// Every endpoint, listed by `GET /`.
+const ENDPOINTS: [&str; 9] = [
+    "GET /",
+    "GET /rosters",
+    "GET /rosters/{name}",
+    "PUT /rosters/{name}",
+    "GET /circuits",
+    "POST /runs/{practice|qualifying|race|weekend|season}",
+    "GET /runs",
+    "GET /runs/{id}",
+    "GET /runs/{id}/standings",
+];
+
+/// Options for a run, named like the command-line options. All of them can be left out.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RunOptions {
+    roster: Option<String>,  // a built-in or uploaded roster, 2024 by default
+    circuit: Option<String>, // part of a circuit's name or country
+    seed: Option<u64>,       // of the race, or of the season
+    laps: Option<u8>,
+    weather: Option<String>,
+    rounds: Option<u8>,         // seasons only
+    substitutions: Vec<String>, // written like on the command line, `SAR:OSU:FP1:rookie`
+}
+
+/// An HTTP status with the JSON body to send back.
+struct Reply {
+    status: u16,
+    body: Value,
+}
+
+impl Reply {
+    fn ok(body: Value) -> Reply {
+        Reply { status: 200, body }
+    }
+
+    fn created(body: Value) -> Reply {
+        Reply { status: 201, body }
+    }
+
+    fn error(status: u16, message: impl Display) -> Reply {
+        Reply {
+            status,
+            body: json!({ "error": message.to_string() }),
+        }
+    }
+}
+
+/// Rosters uploaded since the server started and the latest runs, kept in memory only.
+#[derive(Default)]
+struct Api {
+    rosters: BTreeMap<String, Roster>,
+    runs: VecDeque<Value>, // oldest first, at most MAX_RUNS
+    runs_made: usize,      // so IDs aren't reused once old runs are forgotten
+}
+
+impl Api {
+    fn handle(&mut self, method: &Method, url: &str, body: &str) -> Result<Reply, Reply> {
+        let path = url.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+
+        match (method, segments.as_slice()) {
+            (Method::Get, []) => Ok(Reply::ok(json!({ "endpoints": ENDPOINTS }))),
+            (Method::Get, ["rosters"]) => {
+                let mut names = Roster::available();
+                names.extend(self.rosters.keys().cloned());
+                names.sort();
+                names.dedup();
+                Ok(Reply::ok(json!({ "rosters": names })))
+            }
+            (Method::Get, ["rosters", name]) => Ok(Reply::ok(json!(self.roster(name)?))),
+            (Method::Put, ["rosters", name]) => self.upload_roster(name, body),
+            (Method::Get, ["circuits"]) => {
+                let circuits: Vec<Value> = create_calendar()
+                    .iter()
+                    .chain(&create_reserve_circuits())
+                    .map(|circuit| {
+                        json!({
+                            "name": circuit.name,
+                            "country": circuit.country,
+                            "laps": circuit.laps,
+                        })
+                    })
+                    .collect();
+                Ok(Reply::ok(json!({ "circuits": circuits })))
+            }
+            (Method::Post, ["runs", kind]) => {
+                let options = if body.trim().is_empty() {
+                    RunOptions::default()
+                } else {
+                    serde_json::from_str(body)
+                        .map_err(|error| Reply::error(400, format!("bad run options: {}", error)))?
+                };
+                self.run(kind, options)
+            }
+            (Method::Get, ["runs"]) => {
+                let runs: Vec<Value> = self
+                    .runs
+                    .iter()
+                    .map(|run| {
+                        json!({
+                            "id": run["id"],
+                            "kind": run["kind"],
+                            "roster": run["roster"],
+                            "seed": run["seed"],
+                        })
+                    })
+                    .collect();
+                Ok(Reply::ok(json!({ "runs": runs })))
+            }
+            (Method::Get, ["runs", id]) => Ok(Reply::ok(self.find_run(id)?.clone())),
+            (Method::Get, ["runs", id, "standings"]) => match self.find_run(id)?.get("standings") {
+                Some(standings) => Ok(Reply::ok(standings.clone())),
+                None => Err(Reply::error(
+                    404,
+                    format!("run {} has no race to score", id),
+                )),
+            },
+            _ => Err(Reply::error(
+                404,
+                format!("no endpoint {} {}, see GET /", method, path),
+            )),
+        }
+    }
+
+    /// An uploaded roster, or one of the rosters the command line can load by name.
+    fn roster(&self, name: &str) -> Result<Roster, Reply> {
+        if let Some(roster) = self.rosters.get(name) {
+            return Ok(roster.clone());
+        }
+        check_name(name)?;
+
+        Roster::load(name).map_err(|error| match error {
+            RosterError::Io(_) => Reply::error(404, format!("no roster named {}", name)),
+            error => Reply::error(500, format!("could not load roster {}: {}", name, error)),
+        })
+    }
+
+    /// Stores a roster sent as JSON, replacing an earlier upload of the same name. Every
+    /// problem is listed when it fails validation.
+    fn upload_roster(&mut self, name: &str, body: &str) -> Result<Reply, Reply> {
+        check_name(name)?;
+        let roster: Roster = serde_json::from_str(body)
+            .map_err(|error| Reply::error(400, format!("bad roster: {}", error)))?;
+
+        let problems: Vec<String> = roster
+            .validate()
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect();
+        if !problems.is_empty() {
+            return Err(Reply {
+                status: 422,
+                body: json!({
+                    "error": format!("roster {} has {} problems", name, problems.len()),
+                    "problems": problems,
+                }),
+            });
+        }
+
+        let summary = json!({
+            "name": name,
+            "season": roster.season,
+            "teams": roster.teams.len(),
+            "drivers": roster.drivers.len(),
+        });
+        self.rosters.insert(name.to_string(), roster);
+
+        Ok(Reply::created(summary))
+    }
+
+    fn find_run(&self, id: &str) -> Result<&Value, Reply> {
+        id.parse::<usize>()
+            .ok()
+            .and_then(|id| self.runs.iter().find(|run| run["id"] == id))
+            .ok_or_else(|| Reply::error(404, format!("no run {}, or it was forgotten", id)))
+    }
+
+    /// Keeps a finished run, forgetting the oldest once there are more than `MAX_RUNS`.
+    fn keep(&mut self, run: Value) {
+        self.runs.push_back(run);
+        if self.runs.len() > MAX_RUNS {
+            self.runs.pop_front();
+        }
+    }
+
+    /// Runs a session, weekend or season and keeps the results, which are also sent back.
+    /// Races are scored as if they were the only round, so every race has standings to fetch.
+    fn run(&mut self, kind: &str, options: RunOptions) -> Result<Reply, Reply> {
+        let roster_name = options.roster.as_deref().unwrap_or(DEFAULT_ROSTER);
+        let world = self
+            .roster(roster_name)?
+            .to_world()
+            .map_err(|error| Reply::error(500, error))?;
+        let config = SimulationConfig {
+            circuit: options.circuit,
+            laps: options.laps,
+            weather: match &options.weather {
+                Some(name) => Weather::from_name(name)
+                    .ok_or_else(|| Reply::error(400, "weather must be dry, damp or wet"))?,
+                None => Weather::default(),
+            },
+            seed: options.seed,
+            substitutions: options
+                .substitutions
+                .iter()
+                .map(|spec| {
+                    PlannedSubstitution::parse(spec).ok_or_else(|| {
+                        Reply::error(
+                            400,
+                            format!(
+                                "substitution `{}` should look like SAR:OSU:FP1:rookie",
+                                spec
+                            ),
+                        )
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            ..SimulationConfig::default()
+        };
+
+        let mut reporter = JsonReporter::new(Box::new(io::sink()));
+        let mut simulator = match kind {
+            // A season goes round the calendar, so there's no circuit to find
+            "season" => Simulator::with_world(
+                world,
+                SimulationConfig {
+                    circuit: None,
+                    ..config
+                },
+            ),
+            "practice" | "qualifying" | "race" | "weekend" => Simulator::with_world(world, config),
+            _ => {
+                return Err(Reply::error(
+                    404,
+                    format!(
+                        "no such run `{}`, use practice, qualifying, race, weekend or season",
+                        kind
+                    ),
+                ));
+            }
+        }
+        .map_err(|error| Reply::error(400, error))?;
+
+        match kind {
+            "practice" => simulator.practice(&mut reporter).map(drop),
+            "qualifying" => simulator.qualifying(&mut reporter).map(drop),
+            "race" => simulator.race(&mut reporter).map(|report| {
+                reporter.standings(&simulator.world, &Standings::from_race(&report.results));
+            }),
+            "weekend" => simulator.weekend(None, &mut reporter).map(|weekend| {
+                reporter.standings(&weekend.world, &Standings::from_race(&weekend.race.results));
+            }),
+            _ => simulator.season(options.rounds, &mut reporter).map(drop),
+        }
+        .map_err(failed)?;
+
+        let mut run = serde_json::Map::new();
+        self.runs_made += 1;
+        run.insert("id".to_string(), json!(self.runs_made));
+        run.insert("kind".to_string(), json!(kind));
+        run.insert("roster".to_string(), json!(roster_name));
+        // Sending the seed back with the same options repeats the run
+        run.insert("seed".to_string(), json!(simulator.seed));
+        run.extend(reporter.into_document());
+
+        let run = Value::Object(run);
+        self.keep(run.clone());
+
+        Ok(Reply::created(run))
+    }
+}
+
+fn failed(error: impl Display) -> Reply {
+    Reply::error(500, format!("simulation failed: {}", error))
+}
+
+/// Roster names are looked up in the roster folder, so they can't be paths.
+fn check_name(name: &str) -> Result<(), Reply> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(Reply::error(
+            400,
+            format!("roster names use letters, digits, - and _, not `{}`", name),
+        ))
+    }
+}
+
+fn read_body(request: &mut Request) -> Result<String, Reply> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES)
+        .read_to_string(&mut body)
+        .map_err(|error| Reply::error(400, format!("could not read body: {}", error)))?;
+
+    Ok(body)
+}
+
+/// Serves the JSON API on `address` until the process is stopped, one request at a time.
+/// A line per request goes to `reporter`.
+pub fn run_server(address: &str, reporter: &mut dyn Reporter) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(address)
+        .map_err(|error| format!("could not listen on {}: {}", address, error))?;
+    reporter.note(&format!(
+        "🌐 Serving the simulator API on http://{}",
+        server.server_addr()
+    ));
+    serve(&server, reporter);
+
+    Ok(())
+}
+
+/// Answers requests to `server` until it is shut down.
+fn serve(server: &Server, reporter: &mut dyn Reporter) {
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("a valid header");
+    let mut api = Api::default();
+    for mut request in server.incoming_requests() {
+        let reply = read_body(&mut request)
+            .and_then(|body| api.handle(request.method(), request.url(), &body))
+            .unwrap_or_else(|reply| reply);
+        reporter.note(&format!(
+            "{} {} -> {}",
+            request.method(),
+            request.url(),
+            reply.status
+        ));
+
+        let response = Response::from_string(reply.body.to_string())
+            .with_status_code(reply.status)
+            .with_header(content_type.clone());
+        if let Err(error) = request.respond(response) {
+            reporter.note(&format!("⚠️ Could not answer: {}", error));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Style;
+    use crate::reporter::TerminalReporter;
+    use std::io::Write;
+    use std::net::{SocketAddr, TcpStream};
+    use std::thread;
+
+    /// A server on a free port of localhost, answering on a thread of its own.
+    fn start() -> SocketAddr {
+        let server = Server::http("127.0.0.1:0").expect("a free port");
+        let address = server.server_addr().to_ip().expect("an IP address");
+        thread::spawn(move || serve(&server, &mut TerminalReporter::new(Box::new(io::sink()), Style::Plain)));
+        address
+    }
+
+    /// Sends one request and hands back the status and the JSON body.
+    fn send(address: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn a_race_run_repeats_from_its_seed() {
+        let address = start();
+
+        let (status, first) = send(address, "POST", "/runs/race", r#"{"laps": 5}"#);
+        assert_eq!(status, 201);
+        assert_eq!(first["id"], 1);
+        assert!(first["race"].is_object());
+
+        let options = json!({ "laps": 5, "seed": first["seed"] }).to_string();
+        let (status, second) = send(address, "POST", "/runs/race", &options);
+        assert_eq!(status, 201);
+        assert_eq!(second["race"], first["race"]);
+
+        let (status, stored) = send(address, "GET", "/runs/1/standings", "");
+        assert_eq!(status, 200);
+        assert_eq!(stored, first["standings"]);
+    }
+
+    #[test]
+    fn oldest_runs_are_forgotten() {
+        let mut api = Api::default();
+        for id in 1..=MAX_RUNS + 1 {
+            api.keep(json!({ "id": id }));
+        }
+
+        assert_eq!(api.runs.len(), MAX_RUNS);
+        assert!(api.find_run("1").is_err());
+        assert!(api.find_run(&(MAX_RUNS + 1).to_string()).is_ok());
+    }
+}
@@ -0,0 +1,578 @@
+use crate::error::SimulationError;
+use crate::events::{EventStream, session_start};
+use crate::models::{
+    Car, CarSetup, Circuit, Driver, DriverId, Flag, LongRunPace, PracticeRun, PracticeSession,
+    RaceEvent, RaceReport, RaceResult, RunType, TeamId, TyreCompound,
+};
+use crate::pace::{FUEL_EFFECT_PER_KG, FUEL_PER_LAP_KG, LapConditions, lap_time};
+use crate::race::RaceSimulation;
+use crate::utils::{practice_programme, setup_for};
+use crate::world::World;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Fuel in kilograms for a qualifying lap, just enough for the out lap and the flying lap.
+const QUALIFYING_FUEL_KG: f32 = 5.0;
+
+/// Share of the race distance run in a sprint.
+const SPRINT_DISTANCE: f32 = 1.0 / 3.0;
+
+/// Everything a session is run from.
+pub struct SessionInput<'a> {
+    pub world: &'a World,
+    pub entrants: &'a [DriverId], // reserves already swapped in
+    pub circuit: &'a Circuit,     // with the weather and distance to run
+    pub setups: &'a mut Vec<(DriverId, CarSetup)>, // refined in practice
+    pub grid: &'a [RaceResult],   // a race's starting order, the rest start at the back
+    pub rng: StdRng,
+    pub events: &'a mut EventStream, // every event is written here as it happens
+}
+
+/// What a session hands back once it is over.
+#[derive(Debug, Clone)]
+pub struct SessionOutput {
+    pub session: String,
+    pub classification: Vec<RaceResult>,
+    pub events: Vec<RaceEvent>, // the same events the stream was given, in order
+    pub details: SessionDetails,
+}
+
+/// What a session produced besides its classification.
+#[derive(Debug, Clone)]
+pub enum SessionDetails {
+    Practice(PracticeSession),
+    Qualifying,
+    Race(RaceReport),
+    Sprint(RaceReport),
+}
+
+impl SessionOutput {
+    pub fn practice_session(&self) -> Option<&PracticeSession> {
+        match &self.details {
+            SessionDetails::Practice(session) => Some(session),
+            _ => None,
+        }
+    }
+
+    /// The report of a race or a sprint.
+    pub fn race_report(&self) -> Option<&RaceReport> {
+        match &self.details {
+            SessionDetails::Race(report) | SessionDetails::Sprint(report) => Some(report),
+            _ => None,
+        }
+    }
+}
+
+/// One session of a race weekend. Every format is run from the same input and hands back the
+/// same output, so a weekend is just a list of sessions, see `standard_weekend`.
+pub trait Session {
+    /// What the session is called in results, substitutions and the event stream.
+    fn name(&self) -> &str;
+
+    /// The earlier session whose classification is the starting grid, for sessions with one.
+    fn grid_from(&self) -> Option<&str> {
+        None
+    }
+
+    fn run(&self, input: SessionInput) -> Result<SessionOutput, SimulationError>;
+}
+
+/// A generator for `session` drawn from the weekend's `seed`. Every session gets its own, so
+/// the same seed gives the same weekend and adding a session doesn't change the others.
+pub fn session_rng(seed: u64, session: &str) -> StdRng {
+    // FNV-1a, which unlike the standard hasher won't change between Rust versions and so
+    // keeps saved seeds meaning the same weekend
+    let hash = session
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    StdRng::seed_from_u64(seed ^ hash)
+}
+
+/// FP1 to FP3, qualifying and the race.
+pub fn standard_weekend() -> Vec<Box<dyn Session>> {
+    vec![
+        Box::new(Practice::new("FP1")),
+        Box::new(Practice::new("FP2")),
+        Box::new(Practice::new("FP3")),
+        Box::new(Qualifying::new("Qualifying")),
+        Box::new(Race::after("Qualifying")),
+    ]
+}
+
+/// A sprint weekend: qualifying after FP1 sets the sprint grid on Saturday, and the sprint
+/// result sets the grid for Sunday.
+pub fn sprint_weekend() -> Vec<Box<dyn Session>> {
+    vec![
+        Box::new(Practice::new("FP1")),
+        Box::new(Qualifying::new("Qualifying")),
+        Box::new(Practice::new("FP2")),
+        Box::new(Sprint::after("Qualifying")),
+        Box::new(Race::after("Sprint")),
+    ]
+}
+
+/// A driver taking part, with the car and the setup they start the session with.
+struct Participant<'a> {
+    id: DriverId,
+    driver: &'a Driver,
+    team: TeamId,
+    car: &'a Car,
+    setup: CarSetup,
+}
+
+/// Looks up the car of every entrant. Fails if one has no team to drive for.
+fn participants<'a>(
+    world: &'a World,
+    entrants: &[DriverId],
+    setups: &[(DriverId, CarSetup)],
+) -> Result<Vec<Participant<'a>>, SimulationError> {
+    entrants
+        .iter()
+        .map(|&id| {
+            Ok(Participant {
+                id,
+                driver: world.driver(id),
+                team: world.team_of(id)?,
+                car: world.car_of(id)?,
+                setup: setup_for(setups, id),
+            })
+        })
+        .collect()
+}
+
+/// Orders results by time, quickest first, and numbers the positions.
+fn classify(mut results: Vec<RaceResult>) -> Vec<RaceResult> {
+    results.sort_by(|a, b| a.finish_time_seconds.total_cmp(&b.finish_time_seconds));
+    for (position, result) in results.iter_mut().enumerate() {
+        result.finish_position = (position + 1) as u8;
+    }
+
+    results
+}
+
+/// Writes `event` to the stream as it happens and keeps it in the session's `log`.
+fn record(events: &mut EventStream, log: &mut Vec<RaceEvent>, event: RaceEvent) {
+    events.emit(&event);
+    log.push(event);
+}
+
+/// The start of a session run against the clock rather than wheel to wheel. Hands back the
+/// session's log, to record the rest of its events in.
+fn timed_session_start(input: &mut SessionInput, session: &str) -> Vec<RaceEvent> {
+    let mut log = Vec::new();
+    let start = session_start(
+        input.world,
+        session,
+        &input.circuit.name,
+        None,
+        input.entrants,
+    );
+    record(input.events, &mut log, start);
+    record(
+        input.events,
+        &mut log,
+        RaceEvent::Flag {
+            lap: 0,
+            flag: Flag::Green,
+        },
+    );
+
+    log
+}
+
+fn timed_session_end(
+    session: &str,
+    classification: &[RaceResult],
+    events: &mut EventStream,
+    log: &mut Vec<RaceEvent>,
+) {
+    record(
+        events,
+        log,
+        RaceEvent::Flag {
+            lap: 0,
+            flag: Flag::Chequered,
+        },
+    );
+    record(
+        events,
+        log,
+        RaceEvent::SessionEnd {
+            session: session.to_string(),
+            classification: classification.to_vec(),
+        },
+    );
+}
+
+/// A lap against the clock, recorded as the driver's `lap`th of the session.
+fn timed_lap(
+    participant: &Participant,
+    setup: &CarSetup,
+    circuit: &Circuit,
+    conditions: &LapConditions,
+    session: &str,
+    lap: u8,
+    rng: &mut StdRng,
+) -> (f32, RaceEvent) {
+    let time = lap_time(
+        participant.driver,
+        participant.car,
+        circuit,
+        setup,
+        conditions,
+        rng,
+    );
+    let event = RaceEvent::LapCompleted {
+        session: session.to_string(),
+        lap,
+        driver: participant.id,
+        position: None,
+        lap_time: time,
+        total_time: None,
+        compound: conditions.compound,
+    };
+
+    (time, event)
+}
+
+/// A practice session where every driver works through their team's programme. The setup is
+/// refined after each run, with aero test runs giving the engineers more to work with. The
+/// driver's experience decides how quickly the setup converges on the circuit's optimum.
+/// Drivers are classified by their fastest lap.
+pub struct Practice {
+    name: String, // FP1, FP2 or FP3, which decides the programme
+}
+
+impl Practice {
+    pub fn new(name: &str) -> Practice {
+        Practice {
+            name: name.to_string(),
+        }
+    }
+}
+
+impl Session for Practice {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, mut input: SessionInput) -> Result<SessionOutput, SimulationError> {
+        let participants = participants(input.world, input.entrants, input.setups)?;
+        let mut log = timed_session_start(&mut input, &self.name);
+        let mut runs = Vec::new();
+        let mut long_runs = Vec::new();
+        let mut results = Vec::new();
+
+        for participant in &participants {
+            let teammate_index = participants
+                .iter()
+                .filter(|other| other.team == participant.team)
+                .position(|other| other.id == participant.id)
+                .unwrap_or(0);
+
+            let mut setup = participant.setup;
+            let mut laps_done: u8 = 0;
+
+            for run_type in practice_programme(&self.name, teammate_index) {
+                let mut lap_times = Vec::new();
+                let mut fuel_corrected_total = 0.0;
+
+                for lap in 0..run_type.laps() {
+                    let conditions = LapConditions {
+                        compound: run_type.compound(),
+                        tyre_age: lap as u8,
+                        fuel_kg: run_type.starting_fuel_kg() - lap as f32 * FUEL_PER_LAP_KG,
+                    };
+                    laps_done += 1;
+                    let (time, event) = timed_lap(
+                        participant,
+                        &setup,
+                        input.circuit,
+                        &conditions,
+                        &self.name,
+                        laps_done,
+                        &mut input.rng,
+                    );
+
+                    fuel_corrected_total += time - conditions.fuel_kg * FUEL_EFFECT_PER_KG;
+                    lap_times.push(time);
+                    record(input.events, &mut log, event);
+                }
+
+                if let RunType::LongRun(compound) = run_type {
+                    long_runs.push(LongRunPace {
+                        driver: participant.id,
+                        team: participant.team,
+                        compound,
+                        laps: lap_times.len(),
+                        average_lap_time: lap_times.iter().sum::<f32>() / lap_times.len() as f32,
+                        fuel_corrected_lap_time: fuel_corrected_total / lap_times.len() as f32,
+                    });
+                }
+
+                // Debrief with the engineers before the next run
+                let ideal = &input.circuit.ideal_setup;
+                let experience = participant.driver.experience_level;
+                setup.refine(ideal, experience, &mut input.rng);
+                if run_type == RunType::AeroTest {
+                    setup.refine(ideal, experience, &mut input.rng);
+                }
+
+                runs.push(PracticeRun {
+                    driver: participant.id,
+                    team: participant.team,
+                    run_type,
+                    lap_times,
+                });
+            }
+
+            match input
+                .setups
+                .iter_mut()
+                .find(|(other, _)| *other == participant.id)
+            {
+                Some((_, current)) => *current = setup,
+                None => input.setups.push((participant.id, setup)),
+            }
+
+            let best_lap = runs
+                .iter()
+                .filter(|run| run.driver == participant.id)
+                .map(|run| run.best_lap())
+                .fold(f32::MAX, f32::min);
+            results.push(RaceResult::new(
+                participant.id,
+                participant.team,
+                0,
+                best_lap,
+                false,
+            ));
+        }
+
+        let classification = classify(results);
+        long_runs.sort_by(|a, b| {
+            a.fuel_corrected_lap_time
+                .total_cmp(&b.fuel_corrected_lap_time)
+        });
+        timed_session_end(&self.name, &classification, input.events, &mut log);
+
+        let session = PracticeSession {
+            name: self.name.clone(),
+            results: classification.clone(),
+            runs,
+            long_runs,
+            setup_quality: input
+                .entrants
+                .iter()
+                .map(|&id| {
+                    let setup = setup_for(input.setups, id);
+                    (id, setup.quality(&input.circuit.ideal_setup))
+                })
+                .collect(),
+        };
+
+        Ok(SessionOutput {
+            session: self.name.clone(),
+            classification,
+            events: log,
+            details: SessionDetails::Practice(session),
+        })
+    }
+}
+
+/// A single flying lap for every driver on fresh softs with minimal fuel, just like a
+/// qualifying sim in practice. Classified by lap time.
+pub struct Qualifying {
+    name: String,
+}
+
+impl Qualifying {
+    pub fn new(name: &str) -> Qualifying {
+        Qualifying {
+            name: name.to_string(),
+        }
+    }
+}
+
+impl Session for Qualifying {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run(&self, mut input: SessionInput) -> Result<SessionOutput, SimulationError> {
+        let participants = participants(input.world, input.entrants, input.setups)?;
+        let mut log = timed_session_start(&mut input, &self.name);
+        let conditions = LapConditions {
+            compound: TyreCompound::Soft,
+            tyre_age: 0,
+            fuel_kg: QUALIFYING_FUEL_KG,
+        };
+
+        let mut results = Vec::new();
+        for participant in &participants {
+            let (time, event) = timed_lap(
+                participant,
+                &participant.setup,
+                input.circuit,
+                &conditions,
+                &self.name,
+                1,
+                &mut input.rng,
+            );
+            record(input.events, &mut log, event);
+            results.push(RaceResult::new(
+                participant.id,
+                participant.team,
+                0,
+                time,
+                false,
+            ));
+        }
+
+        let classification = classify(results);
+        timed_session_end(&self.name, &classification, input.events, &mut log);
+
+        Ok(SessionOutput {
+            session: self.name.clone(),
+            classification,
+            events: log,
+            details: SessionDetails::Qualifying,
+        })
+    }
+}
+
+/// Runs a race lap by lap from the grid at `circuit`, which may be shorter than the one in
+/// the input. Without pit stops every car runs the distance on its starting tyres. Laps are
+/// recorded as laps of `session`, whatever the race engine calls them. Hands back the report
+/// and every event of the session.
+fn run_race(
+    session: &str,
+    input: SessionInput,
+    circuit: &Circuit,
+    pit_stops: bool,
+) -> Result<(RaceReport, Vec<RaceEvent>), SimulationError> {
+    let SessionInput {
+        world,
+        entrants,
+        setups,
+        grid,
+        rng,
+        events,
+        ..
+    } = input;
+    let mut race = RaceSimulation::new(world, entrants, circuit, setups, grid, rng)?;
+    if !pit_stops {
+        for car in &mut race.cars {
+            car.planned_stops.clear();
+        }
+    }
+
+    let mut log = Vec::new();
+    let start = session_start(world, session, &circuit.name, Some(circuit.laps), entrants);
+    record(events, &mut log, start);
+    let mut emit_lap = |race: &mut RaceSimulation| {
+        for mut event in race.take_events() {
+            if let RaceEvent::LapCompleted {
+                session: lap_of, ..
+            } = &mut event
+            {
+                *lap_of = session.to_string();
+            }
+            record(events, &mut log, event);
+        }
+    };
+    emit_lap(&mut race);
+    while !race.is_finished() {
+        race.simulate_lap();
+        emit_lap(&mut race);
+    }
+
+    let report = race.into_report();
+    record(
+        events,
+        &mut log,
+        RaceEvent::SessionEnd {
+            session: session.to_string(),
+            classification: report.results.clone(),
+        },
+    );
+
+    Ok((report, log))
+}
+
+/// The race, run lap by lap from the grid of an earlier session. The report has every team
+/// order given. The same generator from the same grid gives the same race.
+pub struct Race {
+    grid_from: String,
+}
+
+impl Race {
+    /// A race starting in the order `session` finished in.
+    pub fn after(session: &str) -> Race {
+        Race {
+            grid_from: session.to_string(),
+        }
+    }
+}
+
+impl Session for Race {
+    fn name(&self) -> &str {
+        "Race"
+    }
+
+    fn grid_from(&self) -> Option<&str> {
+        Some(&self.grid_from)
+    }
+
+    fn run(&self, input: SessionInput) -> Result<SessionOutput, SimulationError> {
+        let circuit = input.circuit;
+        let (report, events) = run_race("Race", input, circuit, true)?;
+
+        Ok(SessionOutput {
+            session: "Race".to_string(),
+            classification: report.results.clone(),
+            events,
+            details: SessionDetails::Race(report),
+        })
+    }
+}
+
+/// A flat-out race over a third of the distance, without pit stops.
+pub struct Sprint {
+    grid_from: String,
+}
+
+impl Sprint {
+    /// A sprint starting in the order `session` finished in.
+    pub fn after(session: &str) -> Sprint {
+        Sprint {
+            grid_from: session.to_string(),
+        }
+    }
+}
+
+impl Session for Sprint {
+    fn name(&self) -> &str {
+        "Sprint"
+    }
+
+    fn grid_from(&self) -> Option<&str> {
+        Some(&self.grid_from)
+    }
+
+    fn run(&self, input: SessionInput) -> Result<SessionOutput, SimulationError> {
+        let mut circuit = input.circuit.clone();
+        circuit.laps = (circuit.laps as f32 * SPRINT_DISTANCE).round().max(1.0) as u8;
+        let (report, events) = run_race("Sprint", input, &circuit, false)?;
+
+        Ok(SessionOutput {
+            session: "Sprint".to_string(),
+            classification: report.results.clone(),
+            events,
+            details: SessionDetails::Sprint(report),
+        })
+    }
+}
@@ -0,0 +1,509 @@
+use crate::error::{RosterError, SimulationError};
+use crate::events::EventStream;
+use crate::export::WeekendResults;
+use crate::fork::ForkPlan;
+use crate::generator::{self, LeagueConfig};
+use crate::market::{self, TransferMove};
+use crate::models::{
+    CarSetup, Circuit, DriverId, PlannedSubstitution, PracticeSession, RaceReport, RaceResult,
+    Substitution, Weather,
+};
+use crate::montecarlo::{self, MonteCarloRow};
+use crate::reporter::Reporter;
+use crate::roster::{DEFAULT_ROSTER, Roster};
+use crate::season::{self, Standings};
+use crate::session::{
+    Practice, Qualifying, Race, Session, SessionInput, SessionOutput, session_rng,
+};
+use crate::utils::*;
+use crate::weekend;
+use crate::world::World;
+use serde::Serialize;
+use std::error::Error;
+
+/// What to simulate with: the grid, the circuit, the conditions and the luck. The defaults
+/// are a dry race at the season opener with the 2024 roster and a random seed.
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub roster: String, // a roster name like `2025` or a path, see `Roster::load`
+    pub generate: Option<u64>, // race a league generated from this seed instead of the roster
+    pub circuit: Option<String>, // part of a circuit's name or country
+    pub laps: Option<u8>, // race distance instead of the circuit's own
+    pub weather: Weather,
+    pub seed: Option<u64>, // of the race, or of every race of a season
+    pub substitutions: Vec<PlannedSubstitution>, // reserves stepping in for the weekend
+}
+
+impl Default for SimulationConfig {
+    fn default() -> SimulationConfig {
+        SimulationConfig {
+            roster: DEFAULT_ROSTER.to_string(),
+            generate: None,
+            circuit: None,
+            laps: None,
+            weather: Weather::default(),
+            seed: None,
+            substitutions: Vec::new(),
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// The generated league or the roster to race with, and which one it was.
+    pub fn world(&self) -> Result<(World, WorldSource), Box<dyn Error>> {
+        if let Some(seed) = self.generate {
+            let world = generator::generate_league(&LeagueConfig::new(seed));
+            return Ok((world, WorldSource::Generated { seed }));
+        }
+
+        let roster = Roster::load(&self.roster).map_err(|error| match error {
+            RosterError::Io(_) => format!(
+                "could not load roster {}: {}\nAvailable rosters: {}",
+                self.roster,
+                error,
+                Roster::available().join(", ")
+            ),
+            error => format!("could not load roster {}: {}", self.roster, error),
+        })?;
+
+        Ok((
+            roster.to_world()?,
+            WorldSource::Roster {
+                season: roster.season,
+            },
+        ))
+    }
+
+    /// The circuit asked for, with the race distance and weather applied.
+    pub fn circuit(&self) -> Result<Circuit, String> {
+        let mut circuit = find_circuit(self.circuit.as_deref())?;
+        self.apply_conditions(&mut circuit);
+
+        Ok(circuit)
+    }
+
+    /// The first `rounds` of the calendar, or all of it, with the distance and weather applied.
+    pub fn calendar(&self, rounds: Option<u8>) -> Vec<Circuit> {
+        let mut calendar = create_calendar();
+        if let Some(rounds) = rounds {
+            calendar.truncate(rounds as usize);
+        }
+        for circuit in &mut calendar {
+            self.apply_conditions(circuit);
+        }
+
+        calendar
+    }
+
+    fn apply_conditions(&self, circuit: &mut Circuit) {
+        if let Some(laps) = self.laps {
+            circuit.laps = laps;
+        }
+        circuit.weather = self.weather;
+    }
+}
+
+/// Where a simulator's world came from, told to the reporter before the first results.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldSource {
+    Roster { season: u16 },
+    Generated { seed: u64 }, // of the league
+}
+
+/// Runs the sessions of a race weekend in order, carrying the setups and the grid from one to
+/// the next, or a whole season. Results go to the reporter given to each session and are
+/// returned too.
+///
+/// Sessions can be skipped: qualifying without practice starts from setups as three practice
+/// sessions would leave them, and a race without qualifying qualifies first.
+pub struct Simulator {
+    pub world: World,
+    pub circuit: Circuit,
+    pub seed: u64,
+    pub events: EventStream, // every event of every session, disabled by default
+    config: SimulationConfig,
+    source: Option<WorldSource>, // until it has been reported
+    substitutions: Vec<Substitution>,
+    setups: Vec<(DriverId, CarSetup)>,
+    history: Vec<SessionOutput>,
+}
+
+impl Simulator {
+    pub fn new(config: SimulationConfig) -> Result<Simulator, Box<dyn Error>> {
+        // The circuit first, so a typo is reported before anything is loaded
+        let circuit = config.circuit()?;
+        let (world, source) = config.world()?;
+
+        Simulator::build(world, Some(source), circuit, config)
+    }
+
+    /// A simulator for a world built elsewhere, like from a roster that isn't in a file.
+    /// `roster` and `generate` in the config are ignored.
+    pub fn with_world(world: World, config: SimulationConfig) -> Result<Simulator, Box<dyn Error>> {
+        let circuit = config.circuit()?;
+
+        Simulator::build(world, None, circuit, config)
+    }
+
+    fn build(
+        world: World,
+        source: Option<WorldSource>,
+        circuit: Circuit,
+        config: SimulationConfig,
+    ) -> Result<Simulator, Box<dyn Error>> {
+        let substitutions = config
+            .substitutions
+            .iter()
+            .map(|planned| planned.resolve(&world))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Simulator {
+            source,
+            seed: config.seed.unwrap_or_else(rand::random),
+            substitutions,
+            setups: create_setups(&world.race_drivers()),
+            events: EventStream::disabled(),
+            history: Vec::new(),
+            world,
+            circuit,
+            config,
+        })
+    }
+
+    /// Sends every event of the sessions run from now on to `events`.
+    pub fn with_events(mut self, events: EventStream) -> Simulator {
+        self.events = events;
+        self
+    }
+
+    /// Reserve drivers stepping in this weekend.
+    pub fn substitutions(&self) -> &[Substitution] {
+        &self.substitutions
+    }
+
+    /// Runs `sessions` after the ones already run, like a whole `sprint_weekend()`. Sessions
+    /// with a grid start from the session it comes from, whichever call ran it.
+    pub fn run_sessions(
+        &mut self,
+        sessions: &[Box<dyn Session>],
+        reporter: &mut dyn Reporter,
+    ) -> Result<Vec<SessionOutput>, SimulationError> {
+        if self.history.is_empty() {
+            self.report_source(reporter);
+            reporter.weekend_start(&self.world, &self.circuit, &self.substitutions);
+        }
+
+        let outputs = weekend::run_sessions(
+            &self.world,
+            &self.circuit,
+            &self.substitutions,
+            &mut self.setups,
+            sessions,
+            &self.history,
+            self.seed,
+            &mut self.events,
+            reporter,
+        )?;
+        self.history.extend(outputs.iter().cloned());
+
+        Ok(outputs)
+    }
+
+    /// Tells `reporter` where the world came from, the first time anything is reported.
+    fn report_source(&mut self, reporter: &mut dyn Reporter) {
+        if let Some(source) = self.source.take() {
+            reporter.world_loaded(&self.world, &source);
+        }
+    }
+
+    /// Every session run so far, in order.
+    pub fn history(&self) -> &[SessionOutput] {
+        &self.history
+    }
+
+    /// FP1 to FP3, where each team works towards the ideal setup.
+    pub fn practice(
+        &mut self,
+        reporter: &mut dyn Reporter,
+    ) -> Result<Vec<PracticeSession>, SimulationError> {
+        let sessions: Vec<Box<dyn Session>> = vec![
+            Box::new(Practice::new("FP1")),
+            Box::new(Practice::new("FP2")),
+            Box::new(Practice::new("FP3")),
+        ];
+        let outputs = self.run_sessions(&sessions, reporter)?;
+
+        Ok(outputs
+            .iter()
+            .filter_map(|output| output.practice_session().cloned())
+            .collect())
+    }
+
+    /// Qualifying, which sets the grid for the race.
+    pub fn qualifying(
+        &mut self,
+        reporter: &mut dyn Reporter,
+    ) -> Result<Vec<RaceResult>, SimulationError> {
+        let practised = self
+            .history
+            .iter()
+            .any(|output| output.practice_session().is_some());
+        if !practised {
+            self.setups = practice_setups(
+                &self.world,
+                &self.world.race_drivers(),
+                &self.circuit,
+                3,
+                &mut session_rng(self.seed, "Practice"),
+            );
+        }
+
+        let mut outputs =
+            self.run_sessions(&[Box::new(Qualifying::new("Qualifying"))], reporter)?;
+
+        Ok(outputs.remove(0).classification)
+    }
+
+    pub fn race(&mut self, reporter: &mut dyn Reporter) -> Result<RaceReport, SimulationError> {
+        self.grid(reporter)?;
+        let outputs = self.run_sessions(&[Box::new(Race::after("Qualifying"))], reporter)?;
+
+        Ok(outputs[0]
+            .race_report()
+            .cloned()
+            .expect("a race always has a report"))
+    }
+
+    /// The grid qualifying set, qualifying first if that hasn't happened yet.
+    fn grid(&mut self, reporter: &mut dyn Reporter) -> Result<Vec<RaceResult>, SimulationError> {
+        match self
+            .history
+            .iter()
+            .find(|output| output.session == "Qualifying")
+        {
+            Some(output) => Ok(output.classification.clone()),
+            None => self.qualifying(reporter),
+        }
+    }
+
+    /// Who races and with which setups, for running the race some other way than `race`,
+    /// like lap by lap. Qualifies first if that hasn't happened yet.
+    pub fn race_entry(
+        &mut self,
+        reporter: &mut dyn Reporter,
+    ) -> Result<RaceEntry, SimulationError> {
+        let grid = self.grid(reporter)?;
+        transfer_setups(&mut self.setups, &self.substitutions, "Race", true);
+
+        Ok(RaceEntry {
+            drivers: session_entrants(&self.world, &self.substitutions, "Race"),
+            setups: self.setups.clone(),
+            grid,
+        })
+    }
+
+    /// The full weekend from the entry list to the chequered flag. A `fork_plan` replays the
+    /// race with a different call.
+    pub fn weekend(
+        &mut self,
+        fork_plan: Option<&ForkPlan>,
+        reporter: &mut dyn Reporter,
+    ) -> Result<WeekendResults, SimulationError> {
+        self.report_source(reporter);
+        weekend::race_weekend(
+            &self.world,
+            &self.circuit,
+            &self.substitutions,
+            self.seed,
+            fork_plan,
+            &mut self.events,
+            reporter,
+        )
+    }
+
+    /// Every round of the calendar, or the first `rounds`, with the championship standings
+    /// at the end. The circuit is ignored, the seed gives every race its own seed.
+    pub fn season(
+        &mut self,
+        rounds: Option<u8>,
+        reporter: &mut dyn Reporter,
+    ) -> Result<Standings, SimulationError> {
+        let calendar = self.config.calendar(rounds);
+        self.report_source(reporter);
+        reporter.season_start(calendar.len(), self.seed);
+        let standings = season::run_season(&self.world, &calendar, self.seed, reporter)?;
+        reporter.standings(&self.world, &standings);
+
+        Ok(standings)
+    }
+
+    /// The transfer market between two seasons, decided on the points in `standings`. Leaves
+    /// `world` with next season's line-ups.
+    pub fn silly_season(
+        &mut self,
+        standings: &Standings,
+        reporter: &mut dyn Reporter,
+    ) -> Vec<TransferMove> {
+        let moves = market::run_silly_season(
+            &mut self.world,
+            &standings.drivers,
+            &mut session_rng(self.seed, "Silly Season"),
+        );
+        reporter.silly_season(&self.world, &moves);
+
+        moves
+    }
+
+    /// The same race `runs` times from one grid, counting how every driver fares.
+    pub fn monte_carlo(
+        &mut self,
+        runs: u32,
+        reporter: &mut dyn Reporter,
+    ) -> Result<Vec<MonteCarloRow>, SimulationError> {
+        let drivers = self.world.race_drivers();
+        let mut setups = practice_setups(
+            &self.world,
+            &drivers,
+            &self.circuit,
+            3,
+            &mut session_rng(self.seed, "Practice"),
+        );
+
+        // Every run starts from the same grid, so only the race itself varies
+        self.report_source(reporter);
+        reporter.weekend_start(&self.world, &self.circuit, &[]);
+        let qualifying = Qualifying::new("Qualifying").run(SessionInput {
+            world: &self.world,
+            entrants: &drivers,
+            circuit: &self.circuit,
+            setups: &mut setups,
+            grid: &[],
+            rng: session_rng(self.seed, "Qualifying"),
+            events: &mut self.events,
+        })?;
+        let grid = qualifying.classification;
+        reporter.monte_carlo_start(&self.world, &grid, runs, self.seed);
+
+        let rows = montecarlo::run_monte_carlo(
+            &self.world,
+            &drivers,
+            &self.circuit,
+            &setups,
+            &grid,
+            runs,
+            self.seed,
+        )?;
+        reporter.monte_carlo(&self.world, &rows, runs);
+
+        Ok(rows)
+    }
+}
+
+/// Everything needed to start the race, see `Simulator::race_entry`.
+#[derive(Debug, Clone)]
+pub struct RaceEntry {
+    pub drivers: Vec<DriverId>,
+    pub setups: Vec<(DriverId, CarSetup)>,
+    pub grid: Vec<RaceResult>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RaceEvent;
+    use crate::reporter::JsonReporter;
+    use serde_json::Value;
+
+    fn simulator(seed: u64) -> Simulator {
+        let config = SimulationConfig {
+            laps: Some(10),
+            seed: Some(seed),
+            ..SimulationConfig::default()
+        };
+
+        Simulator::new(config).expect("the default roster loads")
+    }
+
+    /// Everything a reporter was told, to compare whole runs.
+    fn reported(run: impl FnOnce(&mut JsonReporter)) -> Value {
+        let mut reporter = JsonReporter::new(Box::new(std::io::sink()));
+        run(&mut reporter);
+
+        Value::Object(reporter.into_document())
+    }
+
+    #[test]
+    fn same_seed_repeats_the_weekend() {
+        let weekend = |seed| {
+            reported(|reporter| {
+                let mut simulator = simulator(seed);
+                simulator.practice(reporter).unwrap();
+                simulator.race(reporter).unwrap();
+            })
+        };
+
+        assert_eq!(weekend(7), weekend(7));
+        assert_ne!(weekend(7), weekend(8));
+    }
+
+    #[test]
+    fn same_seed_repeats_the_race_without_practice() {
+        let race = |seed| reported(|reporter| drop(simulator(seed).race(reporter).unwrap()));
+
+        assert_eq!(race(7), race(7));
+    }
+
+    #[test]
+    fn a_race_hands_back_its_events() {
+        let mut simulator = simulator(7);
+        simulator
+            .race(&mut JsonReporter::new(Box::new(std::io::sink())))
+            .unwrap();
+        let race = simulator.history().last().unwrap();
+
+        assert_eq!(race.session, "Race");
+        assert!(matches!(
+            race.events.first(),
+            Some(RaceEvent::SessionStart { session, .. }) if session == "Race"
+        ));
+        assert!(matches!(
+            race.events.last(),
+            Some(RaceEvent::SessionEnd { classification, .. })
+                if classification.len() == race.classification.len()
+        ));
+        let laps = race
+            .events
+            .iter()
+            .filter(|event| matches!(event, RaceEvent::LapCompleted { .. }))
+            .count();
+        assert!(laps >= 10);
+    }
+
+    #[test]
+    fn a_race_can_run_to_the_last_lap_a_u8_holds() {
+        let config = SimulationConfig {
+            laps: Some(255),
+            seed: Some(6),
+            ..SimulationConfig::default()
+        };
+        let mut simulator = Simulator::new(config).unwrap();
+        let report = simulator
+            .race(&mut JsonReporter::new(Box::new(std::io::sink())))
+            .unwrap();
+
+        assert!(report.results.iter().any(|result| !result.dnf));
+    }
+
+    #[test]
+    fn same_seed_repeats_the_season() {
+        let season = |seed| {
+            let standings = simulator(seed)
+                .season(Some(3), &mut JsonReporter::new(Box::new(std::io::sink())))
+                .unwrap();
+            (standings.drivers, standings.teams)
+        };
+
+        assert_eq!(season(3), season(3));
+    }
+}
@@ -1,642 +1,308 @@
-use crate::models::{Car, Driver, RaceResult, Team};
+use crate::models::{
+    CarSetup, Circuit, DriverId, PracticeSession, RaceResult, RunType, Substitution, TeamId,
+    TyreCompound,
+};
+use crate::world::World;
 use rand::Rng;
 
-pub fn create_drivers() -> Vec<Driver> {
-    vec![
-        Driver::new(
-            "Max".to_string(),
-            "Verstappen".to_string(),
-            "Max Verstappen".to_string(),
-            "VER".to_string(),
-            26,
-            "Netherlands".to_string(),
-            1,
-            95,
-            90,
-            85,
-            90,
-            "Oracle Red Bull Racing".to_string(),
-        ),
-        Driver::new(
-            "Sergio".to_string(),
-            "Perez".to_string(),
-            "Sergio Perez".to_string(),
-            "PER".to_string(),
-            34,
-            "Mexico".to_string(),
-            11,
-            88,
-            85,
-            80,
-            85,
-            "Oracle Red Bull Racing".to_string(),
-        ),
-        Driver::new(
-            "Lewis".to_string(),
-            "Hamilton".to_string(),
-            "Lewis Hamilton".to_string(),
-            "HAM".to_string(),
-            39,
-            "United Kingdom".to_string(),
-            44,
-            92,
-            98,
-            75,
-            95,
-            "Mercedes".to_string(),
-        ),
-        Driver::new(
-            "George".to_string(),
-            "Russell".to_string(),
-            "George Russell".to_string(),
-            "RUS".to_string(),
-            26,
-            "United Kingdom".to_string(),
-            63,
-            89,
-            85,
-            78,
-            88,
-            "Mercedes".to_string(),
-        ),
-        Driver::new(
-            "Charles".to_string(),
-            "Leclerc".to_string(),
-            "Charles Leclerc".to_string(),
-            "LEC".to_string(),
-            26,
-            "Monaco".to_string(),
-            16,
-            91,
-            87,
-            82,
-            86,
-            "Scuderia Ferrari".to_string(),
-        ),
-        Driver::new(
-            "Carlos".to_string(),
-            "Sainz".to_string(),
-            "Carlos Sainz".to_string(),
-            "SAI".to_string(),
-            29,
-            "Spain".to_string(),
-            55,
-            88,
-            89,
-            76,
-            87,
-            "Scuderia Ferrari".to_string(),
-        ),
-        Driver::new(
-            "Lando".to_string(),
-            "Norris".to_string(),
-            "Lando Norris".to_string(),
-            "NOR".to_string(),
-            24,
-            "United Kingdom".to_string(),
-            4,
-            90,
-            85,
-            79,
-            89,
-            "McLaren".to_string(),
-        ),
-        Driver::new(
-            "Oscar".to_string(),
-            "Piastri".to_string(),
-            "Oscar Piastri".to_string(),
-            "PIA".to_string(),
-            23,
-            "Australia".to_string(),
-            81,
-            84,
-            78,
-            74,
-            83,
-            "McLaren".to_string(),
-        ),
-        Driver::new(
-            "Fernando".to_string(),
-            "Alonso".to_string(),
-            "Fernando Alonso".to_string(),
-            "ALO".to_string(),
-            42,
-            "Spain".to_string(),
-            14,
-            90,
-            99,
-            70,
-            92,
-            "Aston Martin".to_string(),
-        ),
-        Driver::new(
-            "Lance".to_string(),
-            "Stroll".to_string(),
-            "Lance Stroll".to_string(),
-            "STR".to_string(),
-            25,
-            "Canada".to_string(),
-            18,
-            80,
-            78,
-            76,
-            79,
-            "Aston Martin".to_string(),
-        ),
-        Driver::new(
-            "Esteban".to_string(),
-            "Ocon".to_string(),
-            "Esteban Ocon".to_string(),
-            "OCO".to_string(),
-            27,
-            "France".to_string(),
-            31,
-            84,
-            83,
-            77,
-            84,
-            "Alpine".to_string(),
-        ),
-        Driver::new(
-            "Pierre".to_string(),
-            "Gasly".to_string(),
-            "Pierre Gasly".to_string(),
-            "GAS".to_string(),
-            28,
-            "France".to_string(),
-            10,
-            85,
-            84,
-            78,
-            82,
-            "Alpine".to_string(),
-        ),
-        Driver::new(
-            "Alex".to_string(),
-            "Albon".to_string(),
-            "Alex Albon".to_string(),
-            "ALB".to_string(),
-            28,
-            "Thailand".to_string(),
-            23,
-            86,
-            82,
-            79,
-            84,
-            "Williams".to_string(),
-        ),
-        Driver::new(
-            "Logan".to_string(),
-            "Sargeant".to_string(),
-            "Logan Sargeant".to_string(),
-            "SAR".to_string(),
-            24,
-            "USA".to_string(),
-            2,
-            75,
-            70,
-            80,
-            75,
-            "Williams".to_string(),
-        ),
-        Driver::new(
-            "Kevin".to_string(),
-            "Magnussen".to_string(),
-            "Kevin Magnussen".to_string(),
-            "MAG".to_string(),
-            31,
-            "Denmark".to_string(),
-            20,
-            82,
-            81,
-            82,
-            78,
-            "Haas".to_string(),
-        ),
-        Driver::new(
-            "Nico".to_string(),
-            "Hulkenberg".to_string(),
-            "Nico Hulkenberg".to_string(),
-            "HUL".to_string(),
-            36,
-            "Germany".to_string(),
-            27,
-            83,
-            85,
-            75,
-            80,
-            "Haas".to_string(),
-        ),
-        Driver::new(
-            "Valtteri".to_string(),
-            "Bottas".to_string(),
-            "Valtteri Bottas".to_string(),
-            "BOT".to_string(),
-            35,
-            "Finland".to_string(),
-            77,
-            85,
-            90,
-            70,
-            85,
-            "Stake F1 Team Kick Sauber".to_string(),
-        ),
-        Driver::new(
-            "Zhou".to_string(),
-            "Guanyu".to_string(),
-            "Zhou Guanyu".to_string(),
-            "ZHO".to_string(),
-            25,
-            "China".to_string(),
-            24,
-            80,
-            78,
-            75,
-            77,
-            "Stake F1 Team Kick Sauber".to_string(),
-        ),
-        Driver::new(
-            "Yuki".to_string(),
-            "Tsunoda".to_string(),
-            "Yuki Tsunoda".to_string(),
-            "TSU".to_string(),
-            24,
-            "Japan".to_string(),
-            22,
-            82,
-            80,
-            83,
-            78,
-            "Visa Cash App RB".to_string(),
-        ),
-        Driver::new(
-            "Daniel".to_string(),
-            "Ricciardo".to_string(),
-            "Daniel Ricciardo".to_string(),
-            "RIC".to_string(),
-            35,
-            "Australia".to_string(),
-            3,
-            87,
-            88,
-            74,
-            83,
-            "Visa Cash App RB".to_string(),
-        ),
-    ]
-}
+/// The 2024 calendar, in race order.
+pub fn create_calendar() -> Vec<Circuit> {
+    // (circuit, country, laps, base lap time, tyre degradation, ideal downforce, ride height, balance)
+    let rounds = vec![
+        ("Bahrain International Circuit", "Bahrain", 57, 91.5, 1.4, 60, 45, 50),
+        ("Jeddah Corniche Circuit", "Saudi Arabia", 50, 88.5, 0.8, 35, 40, 55),
+        ("Albert Park Circuit", "Australia", 58, 77.5, 0.9, 55, 50, 50),
+        ("Suzuka International Racing Course", "Japan", 53, 89.0, 1.3, 70, 45, 45),
+        ("Shanghai International Circuit", "China", 56, 93.5, 1.1, 65, 50, 50),
+        ("Miami International Autodrome", "USA", 57, 87.5, 0.9, 50, 45, 55),
+        ("Autodromo Enzo e Dino Ferrari", "Italy", 63, 75.5, 0.9, 65, 50, 50),
+        ("Circuit de Monaco", "Monaco", 78, 71.0, 0.5, 95, 70, 40),
+        ("Circuit Gilles Villeneuve", "Canada", 70, 72.0, 0.8, 35, 55, 55),
+        ("Circuit de Barcelona-Catalunya", "Spain", 66, 72.5, 1.3, 75, 45, 45),
+        ("Red Bull Ring", "Austria", 71, 64.5, 1.0, 45, 50, 50),
+        ("Silverstone Circuit", "United Kingdom", 52, 86.0, 1.3, 70, 40, 45),
+        ("Hungaroring", "Hungary", 70, 76.5, 1.0, 90, 55, 45),
+        ("Circuit de Spa-Francorchamps", "Belgium", 44, 104.0, 1.1, 30, 45, 50),
+        ("Circuit Zandvoort", "Netherlands", 72, 70.5, 1.1, 85, 50, 45),
+        ("Autodromo Nazionale Monza", "Italy", 53, 80.0, 0.8, 10, 35, 55),
+        ("Baku City Circuit", "Azerbaijan", 51, 101.5, 0.7, 25, 40, 55),
+        ("Marina Bay Street Circuit", "Singapore", 62, 90.5, 1.0, 92, 65, 40),
+        ("Circuit of the Americas", "USA", 56, 93.0, 1.2, 70, 55, 50),
+        ("Autodromo Hermanos Rodriguez", "Mexico", 71, 76.0, 0.9, 88, 50, 50),
+        ("Autodromo Jose Carlos Pace", "Brazil", 71, 69.5, 1.1, 60, 60, 50),
+        ("Las Vegas Strip Circuit", "USA", 50, 92.5, 0.7, 20, 35, 55),
+        ("Lusail International Circuit", "Qatar", 57, 80.5, 1.4, 75, 45, 45),
+        ("Yas Marina Circuit", "UAE", 58, 82.5, 0.9, 60, 45, 50),
+    ];
 
-pub fn create_cars() -> Vec<(String, Car)> {
-    vec![
-        (
-            "Oracle Red Bull Racing".to_string(),
-            Car {
-                name: "RB20".to_string(),
-                engine_power: 95,
-                tyre_management: 88,
-                reliability: 92,
-                aerodynamics: 93,
-            },
-        ),
-        (
-            "Mercedes".to_string(),
-            Car {
-                name: "W15".to_string(),
-                engine_power: 90,
-                tyre_management: 85,
-                reliability: 88,
-                aerodynamics: 87,
-            },
-        ),
-        (
-            "Scuderia Ferrari".to_string(),
-            Car {
-                name: "SF-24".to_string(),
-                engine_power: 89,
-                tyre_management: 83,
-                reliability: 87,
-                aerodynamics: 85,
-            },
-        ),
-        (
-            "McLaren".to_string(),
-            Car {
-                name: "MCL38".to_string(),
-                engine_power: 88,
-                tyre_management: 84,
-                reliability: 86,
-                aerodynamics: 89,
-            },
-        ),
-        (
-            "Aston Martin".to_string(),
-            Car {
-                name: "AMR24".to_string(),
-                engine_power: 86,
-                tyre_management: 80,
-                reliability: 85,
-                aerodynamics: 82,
-            },
-        ),
-        (
-            "Alpine".to_string(),
-            Car {
-                name: "A524".to_string(),
-                engine_power: 83,
-                tyre_management: 78,
-                reliability: 82,
-                aerodynamics: 80,
-            },
-        ),
-        (
-            "Williams".to_string(),
-            Car {
-                name: "FW46".to_string(),
-                engine_power: 82,
-                tyre_management: 77,
-                reliability: 81,
-                aerodynamics: 79,
+    rounds
+        .into_iter()
+        .map(
+            |(name, country, laps, lap_time, degradation, downforce, ride_height, balance)| {
+                Circuit::new(
+                    name.to_string(),
+                    country.to_string(),
+                    laps,
+                    lap_time,
+                    degradation,
+                    CarSetup::new(downforce, ride_height, balance),
+                )
             },
-        ),
-        (
-            "Haas".to_string(),
-            Car {
-                name: "VF-24".to_string(),
-                engine_power: 81,
-                tyre_management: 75,
-                reliability: 80,
-                aerodynamics: 78,
-            },
-        ),
-        (
-            "Stake F1 Team Kick Sauber".to_string(),
-            Car {
-                name: "C44".to_string(),
-                engine_power: 80,
-                tyre_management: 74,
-                reliability: 79,
-                aerodynamics: 76,
-            },
-        ),
-        (
-            "Visa Cash App RB".to_string(),
-            Car {
-                name: "VCARB 01".to_string(),
-                engine_power: 82,
-                tyre_management: 76,
-                reliability: 80,
-                aerodynamics: 77,
-            },
-        ),
-    ]
+        )
+        .collect()
 }
 
-pub fn create_teams(drivers: &Vec<Driver>, cars: &Vec<(String, Car)>) -> Vec<Team> {
-    let mut teams = Vec::new();
-
-    // Basic team metadata
-    // (team_name, color, nationality)
-    let team_info = vec![
-        ("Oracle Red Bull Racing", "001344", "Austrian"),
-        ("Mercedes", "C0C0C0", "German"),
-        ("Scuderia Ferrari", "FF2800", "Italian"),
-        ("McLaren", "FF8700", "British"),
-        ("Aston Martin", "006F62", "British"),
-        ("Alpine", "005BAA", "French"),
-        ("Williams", "005AFF", "British"),
-        ("Haas", "FFFFFF", "American"),
-        ("Stake F1 Team Kick Sauber", "4CBB17", "Swiss"),
-        ("Visa Cash App RB", "0033A0", "Italian"),
+/// Venues not on the current calendar that may be added in future seasons.
+pub fn create_reserve_circuits() -> Vec<Circuit> {
+    // (circuit, country, laps, base lap time, tyre degradation, ideal downforce, ride height, balance)
+    let venues = vec![
+        ("Autodromo Internacional do Algarve", "Portugal", 66, 77.0, 1.2, 60, 55, 50),
+        ("Istanbul Park", "Turkey", 58, 83.0, 1.1, 70, 45, 45),
+        ("Nurburgring", "Germany", 60, 88.0, 1.0, 65, 50, 50),
+        ("Hockenheimring", "Germany", 67, 73.0, 1.1, 45, 50, 50),
+        ("Sepang International Circuit", "Malaysia", 56, 93.0, 1.3, 70, 45, 50),
+        ("Kyalami Grand Prix Circuit", "South Africa", 70, 79.0, 1.2, 65, 55, 50),
+        ("Circuit Paul Ricard", "France", 53, 90.0, 1.0, 50, 40, 50),
+        ("Mugello Circuit", "Italy", 59, 75.5, 1.2, 75, 45, 45),
+        ("Buddh International Circuit", "India", 60, 84.0, 1.0, 55, 50, 50),
     ];
 
-    for (_, (team_name, color, nationality)) in team_info.iter().enumerate() {
-        // Get drivers for this team - we need to clone because we need to own the data
-        // to move it into the new Team struct
-        let team_drivers: Vec<Driver> = drivers
+    venues
+        .into_iter()
+        .map(
+            |(name, country, laps, lap_time, degradation, downforce, ride_height, balance)| {
+                Circuit::new(
+                    name.to_string(),
+                    country.to_string(),
+                    laps,
+                    lap_time,
+                    degradation,
+                    CarSetup::new(downforce, ride_height, balance),
+                )
+            },
+        )
+        .collect()
+}
+
+/// A circuit from the calendar or the reserve venues, matched on part of its name or country
+/// ignoring case. The season opener when no name is given.
+pub fn find_circuit(name: Option<&str>) -> Result<Circuit, String> {
+    let mut circuits = create_calendar();
+    circuits.extend(create_reserve_circuits());
+    let Some(name) = name else {
+        return Ok(circuits.remove(0));
+    };
+
+    // Names win over countries, so "spa" is Spa-Francorchamps rather than Spain
+    let wanted = name.to_lowercase();
+    let matching = |field: fn(&Circuit) -> &String| -> Vec<Circuit> {
+        circuits
             .iter()
-            .filter(|d| d.team_name == *team_name)
+            .filter(|circuit| field(circuit).to_lowercase().contains(&wanted))
             .cloned()
-            .collect();
+            .collect()
+    };
+    let mut matches = matching(|circuit| &circuit.name);
+    if matches.is_empty() {
+        matches = matching(|circuit| &circuit.country);
+    }
 
-        // Find the car that matches the team name - need to clone to own the data
-        let car = cars
+    let names = |circuits: &[Circuit]| {
+        circuits
             .iter()
-            .find(|(name, _)| name == team_name)
-            .map(|(_, car)| car.clone()) // Clone to get ownership
-            .unwrap_or_else(|| Car {
-                // Create a default car if needed
-                name: "DefaultCar".to_string(),
-                engine_power: 80,
-                tyre_management: 80,
-                reliability: 80,
-                aerodynamics: 80,
-            });
-
-        // Build the team with the now owned data
-        let team = Team {
-            name: team_name.to_string(),
-            team_color: color.to_string(),
-            nationality: nationality.to_string(),
-            drivers: team_drivers,
-            car,
-        };
-
-        teams.push(team);
+            .map(|circuit| circuit.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match matches.len() {
+        1 => Ok(matches.remove(0)),
+        0 => Err(format!(
+            "no circuit matches \"{}\", choose from: {}",
+            name,
+            names(&circuits)
+        )),
+        _ => Err(format!(
+            "\"{}\" matches more than one circuit: {}",
+            name,
+            names(&matches)
+        )),
     }
-
-    teams
 }
 
-pub fn simulate_practice_session(
-    drivers: &Vec<Driver>,
-    cars: &Vec<(String, Car)>,
+/// Drivers taking part in a session, with reserves swapped in wherever a substitution applies.
+pub fn session_entrants(
+    world: &World,
+    substitutions: &[Substitution],
     session_name: &str,
-) -> Vec<RaceResult> {
-    let mut rng = rand::rng();
-    let mut results = Vec::new();
-
-    for driver in drivers {
-        let car = cars
-            .iter()
-            .find(|(name, _)| name == &driver.team_name)
-            .map(|(_, car)| car) // Getting a reference to the car
-            .unwrap(); // Unwrapping because we assume the car is found
-
-        // Simulate session time based on skill, car attributes, and random variation
-        let time_variation = rng.random_range(-1.5..1.5); // Random variation for practice times
-        let driver_performance = (driver.skill_level as f32 * 0.4)
-            + (car.engine_power as f32 * 0.3)
-            + (car.tyre_management as f32 * 0.2)
-            + (car.reliability as f32 * 0.1)
-            + time_variation;
-
-        let session_time = 90.0 - driver_performance; // 90 seconds is a baseline for the time
-
-        results.push(RaceResult {
-            driver_name: driver.full_name.clone(),
-            team_name: driver.team_name.clone(),
-            finish_position: 0, // Position will be determined later in qualification/race
-            finish_time_seconds: session_time.round() as u8,
-            dnf: false,
-        });
-
-        println!(
-            "{} - {}: {} seconds (Session: {})",
-            driver.full_name,
-            driver.team_name,
-            session_time.round(),
-            session_name
-        );
+) -> Vec<DriverId> {
+    let mut entrants = Vec::new();
+
+    for team in world.teams() {
+        for driver in &team.drivers {
+            let substitute = substitutions
+                .iter()
+                .filter(|sub| sub.applies_to(session_name) && sub.driver_out == *driver)
+                .map(|sub| sub.driver_in)
+                .find(|reserve| team.reserve_drivers.contains(reserve));
+
+            entrants.push(substitute.unwrap_or(*driver));
+        }
     }
 
-    results
+    entrants
 }
 
-pub fn simulate_qualification(drivers: &Vec<Driver>, cars: &Vec<(String, Car)>) -> Vec<RaceResult> {
-    let mut rng = rand::rng();
-    let mut results = Vec::new();
-
-    // Sorting drivers based on their performance and random qualifying factors
-    for driver in drivers {
-        let car = cars
-            .iter()
-            .find(|(name, _)| name == &driver.team_name)
-            .map(|(_, car)| car) // Getting a reference to the car
-            .unwrap(); // Unwrapping because we assume the car is found
-
-        // Similar to the practice, but qualification might have less randomness and more focus on car performance
-        let qualifying_time = (driver.skill_level as f32 * 0.5)
-            + (car.engine_power as f32 * 0.4)
-            + rng.random_range(-0.5..0.5);
-
-        results.push(RaceResult {
-            driver_name: driver.full_name.clone(),
-            team_name: driver.team_name.clone(),
-            finish_position: 0, // To be calculated after sorting
-            finish_time_seconds: qualifying_time.round() as u8,
-            dnf: false,
-        });
-
-        println!(
-            "{} - {}: {} seconds (Qualification)",
-            driver.full_name,
-            driver.team_name,
-            qualifying_time.round()
-        );
-    }
-
-    // Sort by the qualifying time (lower time is better)
-    results.sort_by(|a, b| a.finish_time_seconds.cmp(&b.finish_time_seconds));
+/// Hands the car's setup between a race driver and their substitute around a session, so the
+/// work done in the car isn't lost. Call with `to_substitute` before the session and without it
+/// afterwards.
+pub fn transfer_setups(
+    setups: &mut Vec<(DriverId, CarSetup)>,
+    substitutions: &[Substitution],
+    session_name: &str,
+    to_substitute: bool,
+) {
+    for sub in substitutions.iter().filter(|sub| sub.applies_to(session_name)) {
+        let (from, to) = if to_substitute {
+            (sub.driver_out, sub.driver_in)
+        } else {
+            (sub.driver_in, sub.driver_out)
+        };
+        let setup = setup_for(setups, from);
 
-    // Update positions after sorting
-    for (position, result) in results.iter_mut().enumerate() {
-        result.finish_position = (position + 1) as u8;
+        match setups.iter_mut().find(|(driver, _)| *driver == to) {
+            Some((_, current)) => *current = setup,
+            None => setups.push((to, setup)),
+        }
     }
-
-    results
 }
 
-pub fn simulate_race(drivers: &Vec<Driver>, cars: &Vec<(String, Car)>) -> Vec<RaceResult> {
-    let mut rng = rand::rng();
-    let mut results = Vec::new();
+/// Setup a driver is currently running, or the baseline if their car hasn't been touched yet.
+pub fn setup_for(setups: &[(DriverId, CarSetup)], driver: DriverId) -> CarSetup {
+    setups
+        .iter()
+        .find(|(id, _)| *id == driver)
+        .map(|(_, setup)| *setup)
+        .unwrap_or_else(CarSetup::baseline)
+}
 
-    for driver in drivers {
-        let car = cars
-            .iter()
-            .find(|(name, _)| name == &driver.team_name)
-            .map(|(_, car)| car) // Getting a reference to the car
-            .unwrap(); // Unwrapping because we assume the car is found
+/// Baseline setups for every driver, before any running at a circuit.
+pub fn create_setups(drivers: &[DriverId]) -> Vec<(DriverId, CarSetup)> {
+    drivers
+        .iter()
+        .map(|driver| (*driver, CarSetup::baseline()))
+        .collect()
+}
 
-        // Simulate race performance considering skill, car performance, and some random race-related events
-        let race_performance = (driver.skill_level as f32 * 0.4)
-            + (car.engine_power as f32 * 0.3)
-            + (car.tyre_management as f32 * 0.2)
-            + (car.reliability as f32 * 0.1)
-            + rng.random_range(-3.0..3.0); // Random race event variance
+/// Setups every driver ends up with after `sessions` practice sessions, without simulating the
+/// laps themselves. Used when only the race matters, like in career mode.
+pub fn practice_setups(
+    world: &World,
+    drivers: &[DriverId],
+    circuit: &Circuit,
+    sessions: usize,
+    rng: &mut impl Rng,
+) -> Vec<(DriverId, CarSetup)> {
+    drivers
+        .iter()
+        .map(|&id| {
+            let mut setup = CarSetup::baseline();
+            // Every practice programme has three runs per session
+            for _ in 0..sessions * 3 {
+                setup.refine(
+                    &circuit.ideal_setup,
+                    world.driver(id).experience_level,
+                    rng,
+                );
+            }
+
+            (id, setup)
+        })
+        .collect()
+}
 
-        let race_time = 120.0 - race_performance; // Race time based on performance
+/// Runs each driver does in a practice session. Teammates split their long runs across
+/// compounds so the team learns about both before the race.
+pub fn practice_programme(session_name: &str, teammate_index: usize) -> Vec<RunType> {
+    let race_compound = if teammate_index == 0 {
+        TyreCompound::Medium
+    } else {
+        TyreCompound::Hard
+    };
+
+    match session_name {
+        "FP1" => vec![
+            RunType::AeroTest,
+            RunType::LongRun(race_compound),
+            RunType::QualifyingSim,
+        ],
+        "FP2" => vec![
+            RunType::QualifyingSim,
+            RunType::LongRun(race_compound),
+            RunType::LongRun(TyreCompound::Soft),
+        ],
+        _ => vec![
+            RunType::AeroTest,
+            RunType::QualifyingSim,
+            RunType::QualifyingSim,
+        ],
+    }
+}
 
-        results.push(RaceResult {
-            driver_name: driver.full_name.clone(),
-            team_name: driver.team_name.clone(),
-            finish_position: 0, // Position will be determined after sorting
-            finish_time_seconds: race_time.round() as u8,
-            dnf: rng.random_bool(0.1), // 10% chance of DNF
-        });
+/// Predicted race pace for every team from all long runs done across practice, taking the
+/// fuel load and compound out of the picture. Sorted fastest first.
+pub fn predicted_race_pace(sessions: &[&PracticeSession]) -> Vec<(TeamId, f32)> {
+    let mut predictions: Vec<(TeamId, f32)> = Vec::new();
 
-        println!(
-            "{} - {}: {} seconds (Race)",
-            driver.full_name,
-            driver.team_name,
-            race_time.round()
-        );
-    }
+    for long_run in sessions.iter().flat_map(|session| session.long_runs.iter()) {
+        let pace = long_run.fuel_corrected_lap_time - long_run.compound.pace_offset();
 
-    // First sort by DNF status (non-DNF first), then by finish time
-    results.sort_by(|a, b| {
-        match (a.dnf, b.dnf) {
-            // If both are DNF or both are not DNF, sort by time
-            (true, true) | (false, false) => a.finish_time_seconds.cmp(&b.finish_time_seconds),
-            // If a is DNF but b is not, b comes first
-            (true, false) => std::cmp::Ordering::Greater,
-            // If a is not DNF but b is, a comes first
-            (false, true) => std::cmp::Ordering::Less,
+        match predictions.iter_mut().find(|(team, _)| *team == long_run.team) {
+            Some((_, best)) => *best = best.min(pace),
+            None => predictions.push((long_run.team, pace)),
         }
-    });
-
-    // Update positions after sorting
-    for (position, result) in results.iter_mut().enumerate() {
-        result.finish_position = (position + 1) as u8;
     }
 
-    results
+    predictions.sort_by(|a, b| a.1.total_cmp(&b.1));
+    predictions
 }
 
-pub fn qualification_summary(results: &Vec<RaceResult>) {
-    println!("\n🏁 Qualification Summary (Final Grid Order) 🏁");
-
-    // Define the sections with their titles and corresponding driver ranges
-    let sections = [
-        ("--- Q3 Results ---", 0..10),
-        ("--- Eliminated in Q2 ---", 10..15),
-        ("--- Eliminated in Q1 ---", 15..results.len()),
-    ];
-
-    // Process each section
-    for (title, range) in sections.iter() {
-        println!("\n{}", title);
-
-        // Get the drivers for this section
-        let section_drivers = results
-            .iter()
-            .skip(range.start)
-            .take(range.end - range.start);
-
-        // Print each driver's info
-        for result in section_drivers {
-            println!(
-                "P{}: {} | {} | Time: {}s{}",
-                result.finish_position,
-                result.driver_name,
-                result.team_name,
-                result.finish_time_seconds,
-                if result.dnf { " | DNF" } else { "" }
-            );
-        }
+/// Championship points awarded for a finishing position (top ten score).
+pub fn points_for_position(position: u8) -> u32 {
+    match position {
+        1 => 25,
+        2 => 18,
+        3 => 15,
+        4 => 12,
+        5 => 10,
+        6 => 8,
+        7 => 6,
+        8 => 4,
+        9 => 2,
+        10 => 1,
+        _ => 0,
     }
 }
 
-pub fn race_weekend_summary(results: &Vec<RaceResult>) {
-    println!("\n🏁 Race Weekend Summary 🏁");
-
-    for result in results {
-        println!(
-            "Driver: {} | Team: {} | Position: {} | Time: {}s | DNF: {}",
-            result.driver_name,
-            result.team_name,
-            result.finish_position,
-            result.finish_time_seconds,
-            if result.dnf { "Yes" } else { "No" }
-        );
+/// Points for a finishing position in a sprint (top eight score).
+pub fn sprint_points_for_position(position: u8) -> u32 {
+    match position {
+        1..=8 => 9 - position as u32,
+        _ => 0,
     }
 }
+
+/// Points scored by every driver in a race. Retired drivers score nothing.
+pub fn championship_points(results: &[RaceResult]) -> Vec<(DriverId, u32)> {
+    results
+        .iter()
+        .map(|result| {
+            let points = if result.dnf {
+                0
+            } else {
+                points_for_position(result.finish_position)
+            };
+
+            (result.driver, points)
+        })
+        .collect()
+}
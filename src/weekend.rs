@@ -0,0 +1,174 @@
+use crate::error::SimulationError;
+use crate::events::EventStream;
+use crate::export::WeekendResults;
+use crate::fork::{self, ForkPlan};
+use crate::models::{CarSetup, Circuit, DriverId, PracticeSession, RaceResult, Substitution};
+use crate::race::RaceSimulation;
+use crate::reporter::Reporter;
+use crate::session::{
+    Session, SessionDetails, SessionInput, SessionOutput, session_rng, standard_weekend,
+};
+use crate::utils::*;
+use crate::world::World;
+
+/// Runs `sessions` in order, with reserves driving wherever a substitution applies to the
+/// session. Each session starts from the setups the last one left, and sessions with a grid
+/// start in the order of the session it comes from, run now or in `earlier`. Every session
+/// draws on its own generator from `seed`. Once practice is over, the race pace the long
+/// runs point to is reported too.
+#[allow(clippy::too_many_arguments)]
+pub fn run_sessions(
+    world: &World,
+    circuit: &Circuit,
+    substitutions: &[Substitution],
+    setups: &mut Vec<(DriverId, CarSetup)>,
+    sessions: &[Box<dyn Session>],
+    earlier: &[SessionOutput],
+    seed: u64,
+    events: &mut EventStream,
+    reporter: &mut dyn Reporter,
+) -> Result<Vec<SessionOutput>, SimulationError> {
+    let mut outputs: Vec<SessionOutput> = Vec::new();
+
+    for session in sessions {
+        let name = session.name();
+        let grid = session
+            .grid_from()
+            .and_then(|from| {
+                outputs
+                    .iter()
+                    .chain(earlier)
+                    .find(|output| output.session == from)
+            })
+            .map(|output| output.classification.clone())
+            .unwrap_or_default();
+
+        let entrants = session_entrants(world, substitutions, name);
+        transfer_setups(setups, substitutions, name, true);
+        let output = session.run(SessionInput {
+            world,
+            entrants: &entrants,
+            circuit,
+            setups,
+            grid: &grid,
+            rng: session_rng(seed, name),
+            events: &mut *events,
+        })?;
+        transfer_setups(setups, substitutions, name, false);
+
+        if output.practice_session().is_none() && ends_with_practice(&outputs) {
+            report_race_pace(world, earlier, &outputs, reporter);
+        }
+        match &output.details {
+            SessionDetails::Practice(practice) => reporter.practice_session(world, practice),
+            SessionDetails::Qualifying => reporter.qualifying(world, &output.classification),
+            SessionDetails::Race(report) => reporter.race(world, report),
+            SessionDetails::Sprint(report) => reporter.sprint(world, report),
+        }
+        outputs.push(output);
+    }
+    if ends_with_practice(&outputs) {
+        report_race_pace(world, earlier, &outputs, reporter);
+    }
+
+    Ok(outputs)
+}
+
+fn ends_with_practice(outputs: &[SessionOutput]) -> bool {
+    outputs
+        .last()
+        .is_some_and(|output| output.practice_session().is_some())
+}
+
+/// Race pace predicted from the long runs of every practice session so far.
+fn report_race_pace(
+    world: &World,
+    earlier: &[SessionOutput],
+    outputs: &[SessionOutput],
+    reporter: &mut dyn Reporter,
+) {
+    let practice: Vec<&PracticeSession> = earlier
+        .iter()
+        .chain(outputs)
+        .filter_map(SessionOutput::practice_session)
+        .collect();
+    reporter.race_pace(world, &predicted_race_pace(&practice));
+}
+
+/// Simulates a full race weekend at `circuit`, with reserves stepping in wherever one of
+/// `substitutions` applies. Returns the results of every session.
+#[allow(clippy::too_many_arguments)]
+pub fn race_weekend(
+    world: &World,
+    circuit: &Circuit,
+    substitutions: &[Substitution],
+    race_seed: u64,
+    fork_plan: Option<&ForkPlan>,
+    events: &mut EventStream,
+    reporter: &mut dyn Reporter,
+) -> Result<WeekendResults, SimulationError> {
+    let mut setups = create_setups(&world.race_drivers());
+    reporter.entry_list(world);
+    reporter.weekend_start(world, circuit, substitutions);
+
+    let outputs = run_sessions(
+        world,
+        circuit,
+        substitutions,
+        &mut setups,
+        &standard_weekend(),
+        &[],
+        race_seed,
+        events,
+        reporter,
+    )?;
+    let practice: Vec<PracticeSession> = outputs
+        .iter()
+        .filter_map(|output| output.practice_session().cloned())
+        .collect();
+    let qualifying = session_classification(&outputs, "Qualifying");
+    let race = outputs
+        .iter()
+        .find_map(SessionOutput::race_report)
+        .cloned()
+        .expect("every weekend ends with a race");
+
+    // What if? The race is rebuilt from the same grid and seed, so up to the fork it is the
+    // race above
+    let fork = match fork_plan {
+        Some(plan) => {
+            let race_drivers = session_entrants(world, substitutions, "Race");
+            let race = RaceSimulation::new(
+                world,
+                &race_drivers,
+                circuit,
+                &setups,
+                &qualifying,
+                session_rng(race_seed, "Race"),
+            )?;
+            let outcome = fork::run_fork(world, race, plan, &mut session_rng(race_seed, "Fork"))?;
+            reporter.fork(world, &outcome);
+            Some(outcome)
+        }
+        None => None,
+    };
+
+    Ok(WeekendResults {
+        world: world.clone(),
+        seed: race_seed,
+        circuit: circuit.name.clone(),
+        practice,
+        qualifying,
+        race,
+        fork,
+    })
+}
+
+/// The classification of the session called `name`, empty if it wasn't run.
+fn session_classification(outputs: &[SessionOutput], name: &str) -> Vec<RaceResult> {
+    outputs
+        .iter()
+        .find(|output| output.session == name)
+        .map(|output| output.classification.clone())
+        .unwrap_or_default()
+}
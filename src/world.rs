@@ -0,0 +1,186 @@
+use crate::error::SimulationError;
+use crate::models::{Car, CarId, Driver, DriverId, Team, TeamId, TeamOrderPolicy};
+use serde::{Deserialize, Serialize};
+
+/// Owns every driver, team and car and resolves the relations between them. IDs are indices
+/// into the lists, so every lookup is O(1), and nothing is ever removed so an ID handed out once
+/// stays valid (retired drivers simply no longer belong to a team or the free-agent market).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct World {
+    drivers: Vec<Driver>,
+    teams: Vec<Team>,
+    cars: Vec<Car>,
+    free_agents: Vec<DriverId>,
+}
+
+impl World {
+    pub fn new() -> World {
+        World::default()
+    }
+
+    pub fn add_car(&mut self, mut car: Car) -> CarId {
+        let id = CarId(self.cars.len() as u32);
+        car.id = id;
+        self.cars.push(car);
+        id
+    }
+
+    pub fn add_team(&mut self, mut team: Team) -> TeamId {
+        let id = TeamId(self.teams.len() as u32);
+        team.id = id;
+        self.teams.push(team);
+        id
+    }
+
+    /// Adds a driver to the free-agent market.
+    pub fn add_driver(&mut self, mut driver: Driver) -> DriverId {
+        let id = DriverId(self.drivers.len() as u32);
+        driver.id = id;
+        driver.team = None;
+        self.drivers.push(driver);
+        self.free_agents.push(id);
+        id
+    }
+
+    pub fn driver(&self, id: DriverId) -> &Driver {
+        &self.drivers[id.0 as usize]
+    }
+
+    pub fn driver_mut(&mut self, id: DriverId) -> &mut Driver {
+        &mut self.drivers[id.0 as usize]
+    }
+
+    pub fn team(&self, id: TeamId) -> &Team {
+        &self.teams[id.0 as usize]
+    }
+
+    pub fn team_mut(&mut self, id: TeamId) -> &mut Team {
+        &mut self.teams[id.0 as usize]
+    }
+
+    pub fn car(&self, id: CarId) -> &Car {
+        &self.cars[id.0 as usize]
+    }
+
+    pub fn car_mut(&mut self, id: CarId) -> &mut Car {
+        &mut self.cars[id.0 as usize]
+    }
+
+    /// Every driver that has ever been part of the world, including retired ones.
+    pub fn all_drivers(&self) -> &[Driver] {
+        &self.drivers
+    }
+
+    pub fn cars(&self) -> &[Car] {
+        &self.cars
+    }
+
+    pub fn teams(&self) -> &[Team] {
+        &self.teams
+    }
+
+    pub fn team_ids(&self) -> Vec<TeamId> {
+        self.teams.iter().map(|team| team.id).collect()
+    }
+
+    /// Race drivers of every team, in team order.
+    pub fn race_drivers(&self) -> Vec<DriverId> {
+        self.teams
+            .iter()
+            .flat_map(|team| team.drivers.iter().copied())
+            .collect()
+    }
+
+    pub fn free_agents(&self) -> &[DriverId] {
+        &self.free_agents
+    }
+
+    pub fn driver_name(&self, id: DriverId) -> &str {
+        &self.driver(id).full_name
+    }
+
+    pub fn team_name(&self, id: TeamId) -> &str {
+        &self.team(id).name
+    }
+
+    /// The team the driver races for. Fails for free agents, who have no car to race.
+    pub fn team_of(&self, driver: DriverId) -> Result<TeamId, SimulationError> {
+        self.driver(driver)
+            .team
+            .ok_or_else(|| SimulationError::NoTeam {
+                driver: self.driver_name(driver).to_string(),
+            })
+    }
+
+    /// The car the driver races, through their team.
+    pub fn car_of(&self, driver: DriverId) -> Result<&Car, SimulationError> {
+        let team = self.team_of(driver)?;
+
+        Ok(self.car(self.team(team).car))
+    }
+
+    pub fn find_driver(&self, full_name: &str) -> Option<DriverId> {
+        self.drivers
+            .iter()
+            .find(|driver| driver.full_name == full_name)
+            .map(|driver| driver.id)
+    }
+
+    pub fn find_team(&self, name: &str) -> Option<TeamId> {
+        self.teams
+            .iter()
+            .find(|team| team.name == name)
+            .map(|team| team.id)
+    }
+
+    /// Renames a team, e.g. after a title sponsor change. Everything refers to the team by ID,
+    /// so the new name shows up everywhere.
+    pub fn rename_team(&mut self, id: TeamId, name: &str) {
+        self.team_mut(id).name = name.to_string();
+    }
+
+    /// Gives a driver a race seat at `team`, taking them out of whatever seat or market they
+    /// were in before. Contracts are handled separately with `Driver::sign_contract`.
+    pub fn sign_race_driver(&mut self, driver: DriverId, team: TeamId) {
+        self.detach(driver);
+        self.team_mut(team).drivers.push(driver);
+        self.driver_mut(driver).team = Some(team);
+    }
+
+    /// Makes a driver one of `team`'s reserves.
+    pub fn sign_reserve(&mut self, driver: DriverId, team: TeamId) {
+        self.detach(driver);
+        self.team_mut(team).reserve_drivers.push(driver);
+        self.driver_mut(driver).team = Some(team);
+    }
+
+    /// Puts a driver back on the free-agent market without a contract.
+    pub fn release(&mut self, driver: DriverId) {
+        self.detach(driver);
+        self.free_agents.push(driver);
+        let driver = self.driver_mut(driver);
+        driver.team = None;
+        driver.contract = None;
+    }
+
+    /// Takes a driver out of the sport for good. They stay in the world for the history books.
+    pub fn retire(&mut self, driver: DriverId) {
+        self.detach(driver);
+        let driver = self.driver_mut(driver);
+        driver.team = None;
+        driver.contract = None;
+    }
+
+    /// Removes a driver from every seat list and the free-agent market. A team whose number
+    /// one driver leaves goes back to holding positions.
+    fn detach(&mut self, driver: DriverId) {
+        for team in self.teams.iter_mut() {
+            team.drivers.retain(|id| *id != driver);
+            team.reserve_drivers.retain(|id| *id != driver);
+            if team.order_policy == TeamOrderPolicy::NumberOneDriver(driver) {
+                team.order_policy = TeamOrderPolicy::HoldPosition;
+            }
+        }
+        self.free_agents.retain(|id| *id != driver);
+    }
+}